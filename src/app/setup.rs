@@ -20,6 +20,9 @@ fn configure_resources(app: &mut App) {
         brightness: AMBIENT_LIGHT,
     });
     app.insert_resource(Player::default());
+    // Kept for the whole session, Loading runs again whenever a world is reloaded
+    app.init_resource::<EditHistory>();
+    app.init_resource::<Clipboard>();
 }
 
 fn configure_events(app: &mut App) {
@@ -1,5 +1,11 @@
 use crate::prelude::*;
 
+/// Systems sending world edits (`GameEvent::PlaceBlock` / `RemoveBlock`) or state
+/// changes (`LogicEvent`). Listeners that compare those events with the voxel map
+/// run after all of them and before the handlers, so no event arrives a frame late.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EditSenders;
+
 pub fn register(app: &mut App) {
     register_loading(app);
     register_main_menu(app);
@@ -28,7 +34,7 @@ fn register_in_game(app: &mut App) {
         Update,
         (
             autosave_system,
-            player_input_system,
+            player_input_system.in_set(EditSenders),
             respawn_system,
             raycast_system,
             update_debug_text,
@@ -36,11 +42,44 @@ fn register_in_game(app: &mut App) {
             update_inventory,
             update_identifier,
             update_cursor,
+            block_update_listener
+                .after(EditSenders)
+                .before(logic_event_handler)
+                .before(event_handler),
             logic_event_handler,
-            logic_system,
+            logic_system.in_set(EditSenders),
+            sensor_system.in_set(EditSenders),
+            animate_voxels,
             update_emissive,
         )
             .run_if(in_state(GameState::InGame)),
     );
+    // Edit history and build tools
+    app.add_systems(
+        Update,
+        (
+            record_edits.after(EditSenders).before(event_handler),
+            undo_redo_system.in_set(EditSenders),
+            selection_system.in_set(EditSenders),
+            clipboard_system.in_set(EditSenders),
+            update_paste_preview,
+            array_system.in_set(EditSenders),
+            symmetry_system,
+            line_tool_system.in_set(EditSenders),
+            router_system.in_set(EditSenders),
+            update_tool_readout,
+            focus_widget_inputs,
+            synth_submit_system.in_set(EditSenders),
+            chip_tool_system,
+            chip_submit_system,
+            update_chip_inventory,
+            label_system,
+            label_submit_system,
+            update_world_labels,
+            net_highlight_system.before(update_identifier),
+            update_hover_panel,
+        )
+            .run_if(in_state(GameState::InGame)),
+    );
     app.add_systems(OnExit(GameState::InGame), despawn_all);
 }
\ No newline at end of file
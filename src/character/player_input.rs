@@ -10,6 +10,8 @@ struct PlayerInputContext<'w> {
     player: &'w Player,
     voxel_assets: &'w VoxelMap,
     current_ui: &'w GameUI,
+    tool: &'w ActiveTool,
+    symmetry: &'w Symmetry,
     time: &'w Time,
     has_window: bool,
     place_timer: &'w mut Timer,
@@ -26,6 +28,8 @@ impl<'w, 's> PlayerInputContext<'w> {
         player: &'w Player,
         voxel_assets: &'w VoxelMap,
         current_ui: &'w GameUI,
+        tool: &'w ActiveTool,
+        symmetry: &'w Symmetry,
         time: &'w Time,
         has_window: bool,
         place_timer: &'w mut Timer,
@@ -40,6 +44,8 @@ impl<'w, 's> PlayerInputContext<'w> {
             player,
             voxel_assets,
             current_ui,
+            tool,
+            symmetry,
             time,
             has_window,
             place_timer,
@@ -61,6 +67,19 @@ impl<'w, 's> PlayerInputContext<'w> {
     }
     
     fn handle_ui_shortcuts(&mut self) {
+        // Keys typed into a widget's text input must not trigger other shortcuts
+        if self.current_ui.is_text_widget() {
+            if self.keyboard.just_pressed(KeyCode::Escape) {
+                self.set_ui(GameUI::Default, CursorGrabMode::Locked, false, true);
+            }
+            return;
+        }
+
+        if self.keyboard.just_pressed(KeyCode::F4) && *self.current_ui == GameUI::Default {
+            self.set_ui(GameUI::SynthWidget, CursorGrabMode::None, true, false);
+            return;
+        }
+
         if self.keyboard.just_pressed(KeyCode::Escape) {
             if *self.current_ui != GameUI::ExitMenu {
                 self.set_ui(GameUI::ExitMenu, CursorGrabMode::None, true, false);
@@ -72,9 +91,14 @@ impl<'w, 's> PlayerInputContext<'w> {
 
         if self.keyboard.just_pressed(KeyCode::Tab) {
             if *self.current_ui != GameUI::ExitMenu {
-                let selector = self.player.hotbar_selector;
-                let size = SUBSET_SIZES[selector];
-                self.set_ui(GameUI::Inventory(size), CursorGrabMode::Locked, true, false);
+                // Shift+Tab lists the custom chips instead of the hotbar group
+                if self.keyboard.pressed(KeyCode::ShiftLeft) {
+                    self.set_ui(GameUI::ChipInventory, CursorGrabMode::Locked, true, false);
+                } else {
+                    let selector = self.player.hotbar_selector;
+                    let size = SUBSET_SIZES[selector];
+                    self.set_ui(GameUI::Inventory(size), CursorGrabMode::Locked, true, false);
+                }
             }
             return;
         }
@@ -103,14 +127,18 @@ impl<'w, 's> PlayerInputContext<'w> {
     }
 
     fn handle_world_interactions(&mut self) {
-        if *self.current_ui != GameUI::Default && *self.current_ui != GameUI::ClockWidget {
+        if *self.current_ui != GameUI::Default && !self.current_ui.is_interaction_widget() {
             return;
         }
         if *self.current_ui == GameUI::Default { // Stops certain interactions while not in the default UI state
-            self.handle_block_placement();
-            self.handle_block_removal();
+            // An active build tool takes over the mouse buttons and R
+            if *self.tool == ActiveTool::None {
+                self.handle_block_placement();
+                self.handle_block_removal();
+                self.handle_block_rotation();
+            }
             self.handle_hotbar_copy();
-
+            self.handle_insulation_toggle();
         }
         self.handle_block_interaction();
     }
@@ -126,23 +154,39 @@ impl<'w, 's> PlayerInputContext<'w> {
             return;
         }
 
-        if let Some(mut selected_voxel) = self.player.selected_voxel {
+        if let Some(selected_voxel) = self.player.selected_voxel {
+            let Some(voxel_asset) = self.voxel_assets.asset(selected_voxel.kind).cloned() else {
+                return;
+            };
+
             self.audio_writer
                 .send(AudioEvent::World(WorldSfx::Place, selected_voxel.position));
 
-            selected_voxel.direction = cardinalize(self.player.camera_dir);
-
-            let voxel_asset = self.voxel_assets.asset_map[&selected_voxel.kind].clone();
+            // Holding V stands the block up / down along the camera pitch
+            let vertical = self.keyboard.pressed(KeyCode::KeyV);
+            let direction = placement_direction(self.player.camera_dir, vertical);
+            let selected_voxel = fresh_voxel(selected_voxel.kind, selected_voxel.position, direction);
 
             self.event_writer.send(GameEvent::PlaceBlock {
                 voxel: selected_voxel,
-                voxel_asset,
+                voxel_asset: voxel_asset.clone(),
             });
 
             let mesh_updates = get_neighboring_coords(selected_voxel.position);
             self.event_writer.send(GameEvent::UpdateMesh {
                 updates: mesh_updates,
             });
+
+            // Symmetry mode places the mirror images as well, facing the mirrored way
+            for (position, direction) in self.symmetry.images(selected_voxel.position, direction) {
+                self.event_writer.send(GameEvent::PlaceBlock {
+                    voxel: fresh_voxel(selected_voxel.kind, position, direction),
+                    voxel_asset: voxel_asset.clone(),
+                });
+                self.event_writer.send(GameEvent::UpdateMesh {
+                    updates: get_neighboring_coords(position),
+                });
+            }
         }
     }
 
@@ -169,9 +213,63 @@ impl<'w, 's> PlayerInputContext<'w> {
             self.event_writer.send(GameEvent::UpdateMesh {
                 updates: mesh_updates,
             });
+
+            for (position, _) in self.symmetry.images(hit_voxel.position, hit_voxel.direction) {
+                self.event_writer.send(GameEvent::RemoveBlock { position });
+                self.event_writer.send(GameEvent::UpdateMesh {
+                    updates: get_neighboring_coords(position),
+                });
+            }
         }
     }
 
+    /// R turns the hovered voxel a quarter turn, Shift+R turns it back.
+    fn handle_block_rotation(&mut self) {
+        if !self.keyboard.just_pressed(KeyCode::KeyR) {
+            return;
+        }
+        let Some(hit_voxel) = self.player.hit_voxel else {
+            return;
+        };
+        if get_voxel_rotation_factor(&hit_voxel) == 0.0 {
+            return; // blocks and cables look the same from every side
+        }
+
+        self.event_writer.send(GameEvent::RotateBlock {
+            position: hit_voxel.position,
+            reverse: self.keyboard.pressed(KeyCode::ShiftLeft),
+        });
+
+        let mesh_updates = get_neighboring_coords(hit_voxel.position);
+        self.event_writer.send(GameEvent::UpdateMesh {
+            updates: mesh_updates,
+        });
+    }
+
+    /// I toggles the insulation of the looked-at face of a cable.
+    fn handle_insulation_toggle(&mut self) {
+        if !self.keyboard.just_pressed(KeyCode::KeyI) {
+            return;
+        }
+
+        let (Some(hit_voxel), Some(selected_voxel)) = (self.player.hit_voxel, self.player.selected_voxel) else {
+            return;
+        };
+        if !matches!(hit_voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire) {
+            return;
+        }
+
+        // The placement cell sits right outside the face being looked at
+        let Some(face) = face_index(selected_voxel.position - hit_voxel.position) else {
+            return;
+        };
+
+        self.event_writer.send(GameEvent::ToggleInsulation {
+            position: hit_voxel.position,
+            face,
+        });
+    }
+
     fn handle_hotbar_copy(&mut self) {
         if !self.mouse.just_pressed(MouseButton::Middle) {
             return;
@@ -197,15 +295,14 @@ impl<'w, 's> PlayerInputContext<'w> {
         let pressed = self.keyboard.just_pressed(KeyCode::KeyE);
         let released = self.keyboard.just_released(KeyCode::KeyE);
         
-        if *self.current_ui == GameUI::ClockWidget {
-            let is_looking_at_clock = self
+        if self.current_ui.is_interaction_widget() {
+            let is_looking_at_widget_voxel = self
                 .player
                 .hit_voxel
-                .is_some_and(|voxel| {
-                    matches!(voxel.kind, VoxelType::Component(ComponentVariants::Clock(_)))
-                });
+                .and_then(|voxel| interaction_widget(voxel.kind))
+                .is_some_and(|widget| widget == *self.current_ui);
         
-            if !is_looking_at_clock {
+            if !is_looking_at_widget_voxel {
                 self.set_ui(GameUI::Default, CursorGrabMode::Locked, false, true);
                 return;
             }
@@ -253,9 +350,9 @@ impl<'w, 's> PlayerInputContext<'w> {
             _ => {}
         }
 
-        if matches!(voxel.kind, VoxelType::Component(ComponentVariants::Clock(_))) {
+        if let Some(widget) = interaction_widget(voxel.kind) {
             if pressed == true {
-                self.set_ui(GameUI::ClockWidget, CursorGrabMode::Locked, true, false);
+                self.set_ui(widget, CursorGrabMode::Locked, true, false);
             } else { 
 
                 self.set_ui(GameUI::Default, CursorGrabMode::Locked, false, true);
@@ -294,6 +391,8 @@ pub fn player_input_system(
     mut window_query: Query<&mut Window>,
     event_writer: EventWriter<GameEvent>,
     current_ui: Res<GameUI>,
+    tool: Res<ActiveTool>,
+    symmetry: Res<Symmetry>,
     mut place_timer: Local<Timer>,
     mut remove_timer: Local<Timer>,
     time: Res<Time>,
@@ -308,6 +407,8 @@ pub fn player_input_system(
         player.as_ref(),
         voxel_assets.as_ref(),
         current_ui.as_ref(),
+        tool.as_ref(),
+        symmetry.as_ref(),
         time.as_ref(),
         has_window,
         &mut place_timer,
@@ -320,6 +421,19 @@ pub fn player_input_system(
     context.process();
 }
 
+/// Returns the widget that configures `kind` while E is held, if it has one.
+fn interaction_widget(kind: VoxelType) -> Option<GameUI> {
+    match kind {
+        VoxelType::Component(ComponentVariants::Clock(_)) => Some(GameUI::ClockWidget),
+        VoxelType::Component(ComponentVariants::ProximitySensor(_)) => Some(GameUI::SensorWidget),
+        VoxelType::Component(ComponentVariants::EdgeDetector(_)) => Some(GameUI::EdgeWidget),
+        VoxelType::Component(ComponentVariants::Monostable(_)) => Some(GameUI::MonostableWidget),
+        VoxelType::Component(ComponentVariants::Transmitter(_))
+        | VoxelType::Component(ComponentVariants::Receiver(..)) => Some(GameUI::WirelessWidget),
+        _ => None,
+    }
+}
+
 /// Returns `true` when the provided timer should fire for the given mouse button.
 fn mouse_triggered(
     timer: &mut Timer,
@@ -9,6 +9,7 @@ pub fn raycast_system(
     mut gizmos: Gizmos,
     mut player: ResMut<Player>,
     voxel_map: Res<VoxelMap>,
+    ghosts: Query<(), With<GhostVoxel>>,
 ) {
     // 1. Fetch camera transform or early‑out.
     let camera_tf = if let Ok(tf) = query.get_single() { tf } else { return };
@@ -18,8 +19,10 @@ pub fn raycast_system(
     let cam_fwd   = camera_tf.rotation() * Vec3::new(0.0, 0.0, -1.0);
     let ray       = Ray3d::new(cam_pos, Dir3::new(cam_fwd).expect("bad dir"));
 
-    // 3. Cast & process first hit.
-    if let Some((_, hit)) = raycast.cast_ray(ray, &RayCastSettings::default()).first() {
+    // 3. Cast & process first hit, looking through paste previews.
+    let not_ghost = |entity: Entity| !ghosts.contains(entity);
+    let settings = RayCastSettings::default().with_filter(&not_ghost);
+    if let Some((_, hit)) = raycast.cast_ray(ray, &settings).first() {
         let normal     = hit.normal.round();
         let tri_avg    = hit.triangle
             .map(|tri| (tri[0] + tri[1] + tri[2]) / 3.0)
@@ -44,6 +47,8 @@ pub fn raycast_system(
                 position: adj_pos.as_ivec3(),
                 direction: 0,                                 // set elsewhere
                 state: Bits16::all_zeros(),
+                insulated: 0,
+                extra_states: [Bits16::all_zeros(); MAX_OUTPUT_PORTS - 1],
             })
         } else { None };
         
@@ -63,8 +68,7 @@ pub fn raycast_system(
 
     // 4. Update descriptor based on the *enum* hot‑bar entry.
     player.selected_descriptor = voxel_map
-        .asset_map
-        .get(&player.hotbar[player.hotbar_selector])   // ← enum key
+        .asset(player.hotbar[player.hotbar_selector])   // ← enum key
         .map(|asset| asset.definition.clone());
 
     // 5. Keep camera pose in the player resource.
@@ -16,6 +16,19 @@ pub enum GameEvent {
     RemoveBlock {
         position: IVec3,
     },
+    ToggleInsulation {
+        position: IVec3,
+        face: usize,
+    },
+    RotateBlock {
+        position: IVec3,
+        reverse: bool,
+    },
+    /// Attaches labels, replacing any on the same face. Sent after the
+    /// `PlaceBlock` events of their voxels, so a removal before them can't drop them.
+    AddLabels {
+        labels: Vec<VoxelLabel>,
+    },
     UpdateMesh {
         updates: [IVec3; 6],
     },
@@ -57,7 +70,6 @@ pub fn event_handler(
     mut controller_query: Query<(&mut FpsController, &mut FpsControllerInput)>,
     mut window_query: Query<&mut Window>,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut voxel_query: Query<(Entity, &Voxel)>,
     save_query: Query<(Entity, &Voxel)>,
     mut app_state: ResMut<NextState<GameState>>,
     mut game_ui: ResMut<GameUI>,
@@ -87,24 +99,54 @@ pub fn event_handler(
             GameEvent::PlaceBlock { voxel, voxel_asset } => {
                 let mut voxel_asset_data = voxel_asset.clone();
 
-                let is_valid = matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire);
-                if is_valid {
+                if voxel.kind.uses_cable_mesh() {
                     // Determine cable connections from neighboring voxels.
-                    let connections = count_neighbors(*voxel, &voxel_map);
-                    let texture_row = voxel_map
-                        .asset_map
-                        .get(&voxel.kind)
-                        .map(|asset| asset.texture_row)
-                        .unwrap_or_default();
-
-                    voxel_asset_data.mesh_handle =
-                        meshes.add(create_cable_mesh(texture_row, connections));
+                    voxel_asset_data.mesh_handle = meshes.add(cable_mesh(voxel, &voxel_map));
                 }
                 let new_voxel = voxel.clone();
                 add_voxel(&mut commands, &mut voxel_map, voxel_asset_data, new_voxel, &mut materials);
             }
             GameEvent::RemoveBlock { position } => {
                 remove_voxel(&mut commands, &mut voxel_map, position.clone());
+                // Labels go with the voxel they are attached to
+                game_save.labels.retain(|label| label.position != *position);
+            }
+            GameEvent::RotateBlock { position, reverse } => {
+                let Some(voxel) = voxel_map.voxel_map.get_mut(position) else {
+                    continue;
+                };
+                voxel.direction = rotated_direction(voxel.direction, *reverse);
+                let voxel = *voxel;
+                voxel_map.generation += 1;
+
+                if let Some(&entity) = voxel_map.entity_map.get(position) {
+                    commands.entity(entity).insert((voxel, compute_voxel_transform(&voxel)));
+                    if voxel.kind.uses_cable_mesh() {
+                        update_voxel_cable_mesh(entity, &voxel, &voxel_map, &mut meshes, &mut commands);
+                    }
+                }
+            }
+            GameEvent::ToggleInsulation { position, face } => {
+                let Some(voxel) = voxel_map.voxel_map.get_mut(position) else {
+                    continue;
+                };
+                voxel.insulated ^= 1 << face;
+                let voxel = *voxel;
+                voxel_map.generation += 1;
+
+                if let Some(&entity) = voxel_map.entity_map.get(position) {
+                    commands.entity(entity).insert(voxel);
+                    update_voxel_cable_mesh(entity, &voxel, &voxel_map, &mut meshes, &mut commands);
+                }
+                update_meshes(get_neighboring_coords(*position), &voxel_map, &mut commands, &mut meshes);
+            }
+            GameEvent::AddLabels { labels } => {
+                for label in labels {
+                    game_save
+                        .labels
+                        .retain(|other| (other.position, other.face) != (label.position, label.face));
+                    game_save.labels.push(label.clone());
+                }
             }
             GameEvent::UpdateCursorMode {
                 mode,
@@ -122,7 +164,7 @@ pub fn event_handler(
                 }
             }
             GameEvent::UpdateMesh { updates } => {
-                update_meshes(*updates, &mut voxel_map, &mut commands, &mut meshes, &mut voxel_query);
+                update_meshes(*updates, &voxel_map, &mut commands, &mut meshes);
             }
             GameEvent::SaveWorld { world } => {
                 game_save.world_name = world.world_name.clone();
@@ -130,7 +172,8 @@ pub fn event_handler(
             }
             GameEvent::LoadWorld { world_name } => {
                 game_save.world_name = world_name.clone();
-                load_world(world_name, &mut commands, &mut voxel_map, &mut meshes, &mut materials);
+                game_save.labels =
+                    load_world(world_name, &mut commands, &mut voxel_map, &mut meshes, &mut materials);
             }
             GameEvent::DeleteWorld { world_name } => {
                 delete_world(world_name);
@@ -180,6 +223,15 @@ impl fmt::Display for GameEvent {
             GameEvent::RemoveBlock { position } => {
                 write!(f, "EVENT VOXEL REMOVE: {:?}", position)
             }
+            GameEvent::RotateBlock { position, reverse } => {
+                write!(f, "EVENT VOXEL ROTATE: {:?} reverse {}", position, reverse)
+            }
+            GameEvent::ToggleInsulation { position, face } => {
+                write!(f, "EVENT TOGGLE INSULATION: {:?} face {}", position, face)
+            }
+            GameEvent::AddLabels { labels } => {
+                write!(f, "EVENT ADD LABELS: {}", labels.len())
+            }
             GameEvent::UpdateMesh { updates } => {
                 write!(f, "EVENT MESH UPDATE: {:?}", updates)
             }
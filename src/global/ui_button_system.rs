@@ -33,6 +33,7 @@ pub fn menu_button_system(
     mut audio_writer: EventWriter<AudioEvent>,
     mut logic_event_writer: EventWriter<LogicEvent>,
     voxel_map: Res<VoxelMap>,
+    chip_library: Res<ChipLibrary>,
 ) {
     for (interaction, mut bg_color, menu_action) in query.iter_mut() {
         // Update button color and play sound based on interaction.
@@ -107,6 +108,19 @@ pub fn menu_button_system(
                         });
                     } 
                 }
+                MenuAction::SelectChip(id) => {
+                    let Some(definition) = chip_library.chips.get(id) else {
+                        return;
+                    };
+                    // Chips go into the component slot, like a middle click copy
+                    let kind = VoxelType::Chip(definition.kind());
+                    let mut updated_player = player.clone();
+                    updated_player.hotbar_selector = kind.group();
+                    updated_player.hotbar[kind.group()] = kind;
+                    event_writer.send(GameEvent::ModifyPlayer {
+                        player_modified: updated_player,
+                    });
+                }
                 MenuAction::ClockSetting(speed) => {
                     let Some(hit_voxel) = player.hit_voxel else { 
                         return;
@@ -115,6 +129,41 @@ pub fn menu_button_system(
                     
                     logic_event_writer.send(LogicEvent::UpdateClockVoxel { position: position, new_speed: *speed });
                 }
+                MenuAction::SensorRadius(radius) => {
+                    let Some(hit_voxel) = player.hit_voxel else { 
+                        return;
+                    };
+                    
+                    logic_event_writer.send(LogicEvent::UpdateSensorVoxel { position: hit_voxel.position, new_radius: *radius });
+                }
+                MenuAction::EdgeSetting(mode) => {
+                    let Some(hit_voxel) = player.hit_voxel else { 
+                        return;
+                    };
+                    
+                    logic_event_writer.send(LogicEvent::UpdateEdgeVoxel { position: hit_voxel.position, new_mode: *mode });
+                }
+                MenuAction::PulseWidth(width) => {
+                    let Some(hit_voxel) = player.hit_voxel else { 
+                        return;
+                    };
+                    
+                    logic_event_writer.send(LogicEvent::UpdatePulseVoxel { position: hit_voxel.position, new_width: *width });
+                }
+                MenuAction::Frequency(frequency) => {
+                    let Some(hit_voxel) = player.hit_voxel else { 
+                        return;
+                    };
+                    
+                    logic_event_writer.send(LogicEvent::UpdateWirelessVoxel { position: hit_voxel.position, new_frequency: *frequency });
+                }
+                MenuAction::ReceiverDelay(delayed) => {
+                    let Some(hit_voxel) = player.hit_voxel else { 
+                        return;
+                    };
+                    
+                    logic_event_writer.send(LogicEvent::UpdateReceiverDelay { position: hit_voxel.position, delayed: *delayed });
+                }
                 _ => {}
             }
         }
@@ -124,11 +173,15 @@ pub fn menu_button_system(
     if keyboard_input.just_pressed(KeyCode::Escape)
         && *ui != GameUI::Default
         && *ui != GameUI::ExitMenu
+        && !ui.is_text_widget()
     {
         event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::MainScreen });
     }
     
-    // Delegate text input events to the text listener.
-    edit_text_listener(events, event_writer);
+    // Delegate text input events to the text listener. Only the world creation
+    // screen names worlds; other text inputs handle their own submissions.
+    if *ui == GameUI::NewGame {
+        edit_text_listener(events, event_writer);
+    }
 }
 
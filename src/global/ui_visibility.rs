@@ -13,8 +13,8 @@ pub fn update_ui_visibility(
             // Exact match for all other UI types
             (a, b) if a == b => true,
             
-            (GameUI::Inventory(_), GameUI::Default) => true,
-            (GameUI::ClockWidget, GameUI::Default) => true,
+            (GameUI::Inventory(_) | GameUI::ChipInventory, GameUI::Default) => true,
+            (widget, GameUI::Default) if widget.is_interaction_widget() => true,
             _ => false,
         };
         
@@ -63,9 +63,13 @@ pub fn loading(
     let saved_world = SavedWorld {
         world_name: String::new(),
         voxels: Vec::new(),
+        labels: Vec::new(),
     };
     commands.insert_resource(saved_world);
 
+    // === Custom Chips ===
+    commands.insert_resource(ChipLibrary::load());
+
     // === Voxel Map Creation ===
     commands.insert_resource(create_voxel_map(
         meshes,
@@ -1,13 +1,108 @@
 use std::{
     error::Error,
-    fs::File,
-    io::{BufReader, Write},
+    fs::{self, File},
+    io::Write,
 };
 use bincode;
 use bevy::prelude::*;
+use serde::Deserialize;
 
 use crate::prelude::*;
 
+/// Written in front of the bincode payload, followed by `SAVE_VERSION`.
+/// Files without it predate versioning and are read with the legacy voxel layout.
+const SAVE_MAGIC: &[u8; 3] = b"BNB";
+const SAVE_VERSION: u8 = 3;
+
+/// Written in front of chip definition files, followed by `CHIP_VERSION`.
+const CHIP_MAGIC: &[u8; 3] = b"BNC";
+const CHIP_VERSION: u8 = 1;
+
+/// Voxel layout of unversioned saves, before cables had insulated faces.
+#[derive(Deserialize)]
+struct LegacyVoxel {
+    kind: VoxelType,
+    position: IVec3,
+    direction: u8,
+    state: Bits16,
+}
+
+/// Voxel layout of version 1 saves, before voxels had several output ports.
+#[derive(Deserialize)]
+struct VoxelV1 {
+    kind: VoxelType,
+    position: IVec3,
+    direction: u8,
+    state: Bits16,
+    insulated: u8,
+}
+
+impl From<LegacyVoxel> for Voxel {
+    fn from(v: LegacyVoxel) -> Self {
+        VoxelV1 {
+            kind: v.kind,
+            position: v.position,
+            direction: v.direction,
+            state: v.state,
+            insulated: 0,
+        }
+        .into()
+    }
+}
+
+impl From<VoxelV1> for Voxel {
+    fn from(v: VoxelV1) -> Self {
+        Voxel {
+            kind: v.kind,
+            position: v.position,
+            direction: v.direction,
+            state: v.state,
+            insulated: v.insulated,
+            extra_states: [Bits16::all_zeros(); MAX_OUTPUT_PORTS - 1],
+        }
+    }
+}
+
+/// `SavedWorld` as written before labels, with the voxel layout `V` of its version.
+#[derive(Deserialize)]
+struct OldSavedWorld<V> {
+    world_name: String,
+    voxels: Vec<V>,
+}
+
+impl<V: Into<Voxel>> From<OldSavedWorld<V>> for SavedWorld {
+    fn from(old: OldSavedWorld<V>) -> Self {
+        SavedWorld {
+            world_name: old.world_name,
+            voxels: old.voxels.into_iter().map(Into::into).collect(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+/// Decodes a save file written by any version of `save_world`.
+fn decode_saved_world(bytes: &[u8]) -> Result<SavedWorld, Box<dyn Error>> {
+    let config = bincode::config::standard();
+    match bytes.strip_prefix(SAVE_MAGIC) {
+        Some([version, payload @ ..]) if *version == SAVE_VERSION => {
+            Ok(bincode::serde::decode_from_slice(payload, config)?.0)
+        }
+        Some([2, payload @ ..]) => {
+            let old: OldSavedWorld<Voxel> = bincode::serde::decode_from_slice(payload, config)?.0;
+            Ok(old.into())
+        }
+        Some([1, payload @ ..]) => {
+            let old: OldSavedWorld<VoxelV1> = bincode::serde::decode_from_slice(payload, config)?.0;
+            Ok(old.into())
+        }
+        Some([version, ..]) => Err(format!("unsupported save version {version}").into()),
+        _ => {
+            let old: OldSavedWorld<LegacyVoxel> = bincode::serde::decode_from_slice(bytes, config)?.0;
+            Ok(old.into())
+        }
+    }
+}
+
 pub fn save_world(
     query: &Query<(Entity, &Voxel)>,
     save_game: &SavedWorld,
@@ -18,19 +113,103 @@ pub fn save_world(
     let saved_world = SavedWorld {
         world_name: save_game.world_name.clone(),
         voxels,
+        labels: save_game.labels.clone(),
     };
 
+    let file_path = format!("assets/saves/{}.bin", save_game.world_name);
+    write_saved_world(&file_path, &saved_world)
+}
+
+/// Writes `saved_world` to `file_path` in the current versioned format.
+fn write_saved_world(file_path: &str, saved_world: &SavedWorld) -> Result<(), Box<dyn Error>> {
     // Serialize the saved world using bincode.
-    let serialized = bincode::serde::encode_to_vec(&saved_world, bincode::config::standard())?;
-    
+    let serialized = bincode::serde::encode_to_vec(saved_world, bincode::config::standard())?;
+
     // Save the serialized data to a file.
-    let file_path = format!("assets/saves/{}.bin", save_game.world_name);
     let mut file = File::create(file_path)?;
+    file.write_all(SAVE_MAGIC)?;
+    file.write_all(&[SAVE_VERSION])?;
+    file.write_all(&serialized)?;
+
+    Ok(())
+}
+
+/// Blueprints are stored like a small world with positions relative to their
+/// lowest corner, so older files go through the same version upgrades as saves.
+pub fn save_blueprint(blueprint: &Blueprint) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(BLUEPRINT_PATH)?;
+    let saved = SavedWorld {
+        world_name: blueprint.name.clone(),
+        voxels: blueprint.voxels.clone(),
+        labels: blueprint.labels.clone(),
+    };
+    write_saved_world(&format!("{BLUEPRINT_PATH}{}.bin", blueprint.name), &saved)
+}
+
+pub fn load_blueprint(name: &str) -> Result<Blueprint, Box<dyn Error>> {
+    let bytes = fs::read(format!("{BLUEPRINT_PATH}{name}.bin"))?;
+    let saved = decode_saved_world(&bytes)?;
+    Ok(Blueprint {
+        name: name.to_string(),
+        voxels: saved.voxels,
+        labels: saved.labels,
+    })
+}
+
+/// Names of every blueprint in `BLUEPRINT_PATH`, sorted.
+pub fn blueprint_names() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(BLUEPRINT_PATH)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("bin"))
+        .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn save_chip(definition: &ChipDefinition) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(CHIP_PATH)?;
+    let serialized = bincode::serde::encode_to_vec(definition, bincode::config::standard())?;
+
+    let mut file = File::create(format!("{CHIP_PATH}{}.chip", definition.name))?;
+    file.write_all(CHIP_MAGIC)?;
+    file.write_all(&[CHIP_VERSION])?;
     file.write_all(&serialized)?;
 
     Ok(())
 }
 
+/// Every chip definition in `CHIP_PATH`. Files that can't be read are skipped.
+pub fn load_chips() -> Vec<ChipDefinition> {
+    fs::read_dir(CHIP_PATH)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("chip"))
+        .filter_map(|path| {
+            let bytes = fs::read(&path).ok()?;
+            let payload = match bytes.strip_prefix(CHIP_MAGIC) {
+                Some([version, payload @ ..]) if *version == CHIP_VERSION => payload,
+                _ => {
+                    eprintln!("Skipping unreadable chip {:?}", path);
+                    return None;
+                }
+            };
+            match bincode::serde::decode_from_slice(payload, bincode::config::standard()) {
+                Ok((definition, _)) => Some(definition),
+                Err(error) => {
+                    eprintln!("Skipping unreadable chip {:?}: {error}", path);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 pub fn autosave_system(
     mut autosave_timer: Local<Timer>,
     time: Res<Time>,
@@ -47,27 +226,25 @@ pub fn autosave_system(
     }
 }
 
+/// Spawns the voxels of the saved world and returns its labels.
 pub fn load_world(
     world_name: &str,
     commands: &mut Commands,
     mut voxel_map: &mut ResMut<VoxelMap>,
     mut meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
-) {
+) -> Vec<VoxelLabel> {
     let file_path = format!("assets/saves/{}.bin", world_name);
-    let file = File::open(&file_path).expect("Failed to open file");
-    let reader = BufReader::new(file);
+    let bytes = fs::read(&file_path).expect("Failed to open file");
 
     // Deserialize the saved world.
-    let saved_world: SavedWorld = bincode::serde::decode_from_reader(reader, bincode::config::standard())
-        .expect("Couldn't decode saved world");
+    let saved_world = decode_saved_world(&bytes).expect("Couldn't decode saved world");
 
     // Add each voxel from the saved world to the voxel map.
     for voxel in &saved_world.voxels {
         let voxel_id = voxel.kind;
         let voxel_asset = voxel_map
-            .asset_map
-            .get(&voxel_id)
+            .asset(voxel_id)
             .expect("Failed to get voxel asset")
             .clone();
         add_voxel(commands, &mut voxel_map, voxel_asset, voxel.clone(), materials);
@@ -84,6 +261,9 @@ pub fn load_world(
             VoxelType::BundledWire => {
                 is_valid = true; 
             }
+            VoxelType::Diode | VoxelType::WireCrossing => {
+                is_valid = true; 
+            }
             _ => {}
         }
         if is_valid {
@@ -96,6 +276,8 @@ pub fn load_world(
             update_voxel_cable_mesh(entity, voxel, &voxel_map, &mut meshes, commands);
         }
     }
+
+    saved_world.labels
 }
 
 
@@ -1,3 +1,6 @@
+// Bevy systems take every resource and query they use as an argument
+#![allow(clippy::too_many_arguments)]
+
 // ======================================================================
 // Module Declarations
 // ======================================================================
@@ -8,6 +11,7 @@ mod loading;
 pub mod meta;
 pub mod prelude;
 pub mod simulation;
+mod tools;
 mod ui;
 mod voxel;
 
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::meta::resources::EdgeMode;
+
 /// Enum containing the global world states
 #[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GameState {
@@ -30,6 +32,51 @@ pub struct HotbarSlot {
 #[derive(Component)]
 pub struct VoxelIdentifierText;
 
+/// Marking Component for the text next to the crosshair describing the active build tool.
+#[derive(Component)]
+pub struct ToolReadoutText;
+
+/// Text input that takes the keyboard while the widget it belongs to is open.
+#[derive(Component)]
+pub struct WidgetInput(pub GameUI);
+
+/// Marking Component for the text input of the gate synthesis widget.
+#[derive(Component)]
+pub struct SynthInput;
+
+/// Marking Component for the line under the synthesis input showing errors or the input order.
+#[derive(Component)]
+pub struct SynthStatusText;
+
+/// Marking Component for the text input naming a chip being packaged.
+#[derive(Component)]
+pub struct ChipNameInput;
+
+/// Marking Component for the line under the chip name input showing errors.
+#[derive(Component)]
+pub struct ChipStatusText;
+
+/// Marking Component for the node holding one button per chip in the chip inventory.
+#[derive(Component)]
+pub struct ChipInventoryList;
+
+/// Marking Component for voxels driven by the player instead of a key press
+/// (pressure plates, proximity sensors), so `sensor_system` doesn't walk the whole map.
+#[derive(Component)]
+pub struct SensorVoxel;
+
+/// Marking Component for the panel describing the voxel under the crosshair.
+#[derive(Component)]
+pub struct HoverPanelText;
+
+/// Marking Component for the text input of the label widget.
+#[derive(Component)]
+pub struct LabelInput;
+
+/// Screen text drawing the world label at this index of `SavedWorld::labels`.
+#[derive(Component)]
+pub struct WorldLabelText(pub usize);
+
 /// Marking component for all game entities in the main scene 
 #[derive(Component)]
 pub struct MainMenuEntity;
@@ -49,6 +96,12 @@ pub enum MenuAction {
     SaveAndQuit,
     InventorySlot(usize),
     ClockSetting(usize),
+    SensorRadius(usize),
+    EdgeSetting(EdgeMode),
+    PulseWidth(usize),
+    Frequency(usize),
+    ReceiverDelay(bool),
+    SelectChip(u32), // Chip id
 }
 
 // Marking Component for every UI window
@@ -65,4 +118,31 @@ pub enum GameUI {
     ExitMenu,
     Debug, 
     ClockWidget, 
+    SensorWidget,
+    EdgeWidget,
+    MonostableWidget,
+    WirelessWidget,
+    SynthWidget,
+    ChipWidget,
+    ChipInventory,
+    LabelWidget,
+}
+
+impl GameUI {
+    /// Widgets that are held open with E while looking at a configurable voxel.
+    pub fn is_interaction_widget(self) -> bool {
+        matches!(
+            self,
+            GameUI::ClockWidget
+                | GameUI::SensorWidget
+                | GameUI::EdgeWidget
+                | GameUI::MonostableWidget
+                | GameUI::WirelessWidget
+        )
+    }
+
+    /// Widgets with a text input, which keep every key to themselves until closed.
+    pub fn is_text_widget(self) -> bool {
+        matches!(self, GameUI::SynthWidget | GameUI::ChipWidget | GameUI::LabelWidget)
+    }
 }
\ No newline at end of file
@@ -14,9 +14,9 @@ pub const MAX_RAY_DIST: f32 = 10.0;
 // TEXTURES
 pub const VOXEL_DEFINITITION_PATH: &str = "assets/voxels/voxel_definitions.json";
 pub const VOXEL_TEXTURE_PATH: &str = "textures/texturesMay5.png";
-pub const NUM_VOXELS: usize = 43;
+pub const NUM_VOXELS: usize = 62;
 pub const ROTATION_LOCKED_SETS: usize = 2;
-pub const SUBSET_SIZES: [usize; 9] = [8, 1, 16, 2, 2, 2, 2, 2, 4];
+pub const SUBSET_SIZES: [usize; 9] = [8, 3, 16, 2, 4, 4, 4, 2, 14];
 
 // UI 
 pub const HOTBAR_BORDER_COLOR: Srgba = Srgba::GREEN;
@@ -25,6 +25,7 @@ pub const CURSOR_TEXTURE_PATH: &str = "textures/cursor7.png";
 pub const HOTBAR_SIZE: usize = 9; // Changing this will probably break shit
 pub const INVENTORY_SIZE: usize = 16; // 
 pub const SPEED_INDICATOR_PATH: &str = "textures/speed_indicator.png";
+pub const LABEL_MAX_LENGTH: usize = 32; // characters kept of a voxel label
 
 pub const PRESSED_COLOR: Color = Color::srgb(0.15, 0.90, 0.15);
 pub const HOVER_COLOR: Color = Color::srgb(0.5, 0.60, 0.5);
@@ -37,6 +38,10 @@ pub const PLAYER_HEIGHT: f32 = 1.9;
 pub const PLAYER_CROUCHED_HEIGHT: f32 = 1.5;
 pub const PLAYER_PLACE_DELAY: Duration = Duration::from_millis(150);
 pub const PLAYER_REMOVE_DELAY: Duration = Duration::from_millis(150);
+pub const EDIT_HISTORY_LIMIT: usize = 256; // undo steps kept per world
+pub const SELECTION_MAX_VOLUME: i64 = 32_768; // cells a region operation may touch
+pub const ARRAY_MAX_COUNT: i32 = 64; // slices the array tool stamps at most
+pub const TOOL_MESSAGE_TIME: Duration = Duration::from_secs(4); // how long tool feedback stays in the readout
 pub const SPAWN_POINT: Vec3 = Vec3::new(0.0, 5.625, 0.0);
 pub const RESPAWN_THERESHOLD: f32 = -10.0;
 
@@ -44,6 +49,8 @@ pub const RESPAWN_THERESHOLD: f32 = -10.0;
 pub const TEMP_SAVE_PATH: &str = "assets/saves/temp_save.json";
 pub const AUTOSAVE_TIME: Duration = Duration::from_secs(10);
 pub const SAVE_SLOTS: usize = 4;
+pub const BLUEPRINT_PATH: &str = "assets/blueprints/";
+pub const CHIP_PATH: &str = "assets/saves/chips/"; // Custom chip definitions, next to the worlds
 
 // AUDIO
 pub const AUDIO_PLACE: &str = "audio/place.wav"; 
@@ -56,4 +63,12 @@ pub const UI_VOLUME: f64 = 0.5;
 
 // SIMULATION
 pub const TICK_RATE: u64 = 200;
-pub const SPEED_SETTINGS: [u64; 5] = [0, 4, 16, 64, 256]; 
\ No newline at end of file
+pub const SPEED_SETTINGS: [u64; 5] = [0, 4, 16, 64, 256]; 
+pub const PROXIMITY_RADII: [usize; 4] = [2, 4, 8, 16]; // Selectable proximity sensor radii
+pub const DEFAULT_PROXIMITY_RADIUS: usize = 4;
+pub const PULSE_WIDTHS: [usize; 5] = [1, 2, 4, 8, 16]; // Selectable monostable pulse widths in ticks
+pub const DEFAULT_PULSE_WIDTH: usize = 4;
+pub const WIRELESS_FREQUENCIES: usize = 16; // Frequencies offered by the wireless widget
+pub const MAX_OUTPUT_PORTS: usize = 4; // Output ports a single voxel can drive
+pub const ACTUATOR_ANIMATION_TIME: Duration = Duration::from_millis(150); // Piston / door movement
+pub const DOOR_OPEN_THICKNESS: f32 = 0.125; // Width of an open door panel
\ No newline at end of file
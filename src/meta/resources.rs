@@ -4,6 +4,8 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use bevy_kira_audio::AudioSource;
 
+use crate::meta::config::{DEFAULT_PROXIMITY_RADIUS, DEFAULT_PULSE_WIDTH, MAX_OUTPUT_PORTS, TOOL_MESSAGE_TIME};
+
 #[derive(Resource, Debug, Clone)]
 pub struct Player {
     pub camera_pos: Vec3,
@@ -86,14 +88,65 @@ pub struct VoxelMap {
     pub entity_map: HashMap<IVec3, Entity>, // Entity ids by location
     pub voxel_map: HashMap<IVec3, Voxel>,   // Local voxel values by location
     pub asset_map: HashMap<VoxelType, VoxelAsset>, // global voxel values by VoxelType
+    /// Bumped when a voxel is placed, removed, moved, turned or re-insulated, but
+    /// not on state changes, so systems can tell when the wiring may differ.
+    pub generation: u64,
+}
+
+impl VoxelMap {
+    /// Looks up the shared asset for `kind`, see [`VoxelType::asset_kind`].
+    pub fn asset(&self, kind: VoxelType) -> Option<&VoxelAsset> {
+        self.asset_map.get(&kind.asset_kind())
+    }
 }
 
 #[derive(Component, Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Voxel {
     pub kind: VoxelType,
     pub position: IVec3,
-    pub direction: u8,
+    pub direction: u8, // 1..=4 horizontal, 5..=8 facing up, 9..=12 facing down
     pub state: Bits16,
+    pub insulated: u8, // Cable faces that don't connect, bits in [+X, −X, +Y, −Y, +Z, −Z] order
+    pub extra_states: [Bits16; MAX_OUTPUT_PORTS - 1], // Words on output ports 1.., port 0 is `state`
+}
+
+impl Voxel {
+    /// Word currently on output `port` (see `IoPattern::outputs`).
+    pub fn port_state(&self, port: usize) -> Bits16 {
+        match port {
+            0 => self.state,
+            p => self.extra_states.get(p - 1).copied().unwrap_or(Bits16::all_zeros()),
+        }
+    }
+
+    pub fn set_port_state(&mut self, port: usize, word: Bits16) {
+        match port {
+            0 => self.state = word,
+            p => {
+                if let Some(slot) = self.extra_states.get_mut(p - 1) {
+                    *slot = word;
+                }
+            }
+        }
+    }
+
+    /// True when the face pointing along the unit offset `dir` is insulated.
+    pub fn is_insulated(&self, dir: IVec3) -> bool {
+        face_index(dir).is_some_and(|face| self.insulated & (1 << face) != 0)
+    }
+}
+
+/// Index of a unit offset in `[+X, −X, +Y, −Y, +Z, −Z]` order (see `get_neighboring_coords`).
+pub fn face_index(dir: IVec3) -> Option<usize> {
+    match (dir.x, dir.y, dir.z) {
+        ( 1, 0, 0) => Some(0),
+        (-1, 0, 0) => Some(1),
+        ( 0, 1, 0) => Some(2),
+        ( 0,-1, 0) => Some(3),
+        ( 0, 0, 1) => Some(4),
+        ( 0, 0,-1) => Some(5),
+        _          => None,
+    }
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -107,6 +160,9 @@ pub enum VoxelType {
     Xor(XorVariants),
     Latch(LatchVariants),
     Component(ComponentVariants),
+    Diode,
+    WireCrossing,
+    Chip(ChipKind),
 }
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum StructuralVariants {
@@ -128,16 +184,22 @@ pub enum NotVariants {
 pub enum AndVariants {
     AndGate,
     NandGate,
+    And3Gate,
+    And4Gate,
 }
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum OrVariants {
     OrGate,
     NorGate,
+    Or3Gate,
+    Or4Gate,
 }
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum XorVariants {
     XorGate,
     XnorGate,
+    Xor3Gate,
+    Xor4Gate,
 }
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum LatchVariants{
@@ -150,44 +212,147 @@ pub enum ComponentVariants{
     Switch,
     Button, 
     Clock(usize), 
+    PressurePlate,
+    ProximitySensor(usize), // Radius
+    Piston,
+    Door,
+    UpdateDetector,
+    Random(u16), // Seed
+    EdgeDetector(EdgeMode),
+    Monostable(usize), // Pulse width in ticks
+    Transmitter(usize), // Frequency
+    Receiver(usize, bool), // Frequency, one-tick delay
+}
+
+/// Which input transitions an `EdgeDetector` answers with a one-tick pulse.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EdgeMode {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// Local faces a custom chip can put pins on. Bit `i` of `ChipKind::inputs` /
+/// `ChipKind::outputs` stands for `CHIP_FACES[i]`, and ports are numbered in this order.
+pub const CHIP_FACES: [IVec3; 6] = [
+    IVec3::NEG_X,
+    IVec3::X,
+    IVec3::NEG_Z,
+    IVec3::Z,
+    IVec3::Y,
+    IVec3::NEG_Y,
+];
+
+/// A placed custom chip: which definition it runs and the faces of its pins,
+/// so its ports resolve without looking the definition up.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ChipKind {
+    pub id: u32,
+    pub inputs: u8,
+    pub outputs: u8,
+}
+
+impl ChipKind {
+    fn faces(mask: u8) -> Vec<IVec3> {
+        CHIP_FACES
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, face)| *face)
+            .collect()
+    }
+
+    /// Local offsets of the input pins, in port order.
+    pub fn input_faces(self) -> Vec<IVec3> {
+        Self::faces(self.inputs)
+    }
+
+    /// Local offsets of the output pins, in port order.
+    pub fn output_faces(self) -> Vec<IVec3> {
+        Self::faces(self.outputs)
+    }
+}
+
+/// A named output of a voxel; `offset` is local (+Z forward).
+/// Port 0 drives `Voxel::state`, the others `Voxel::extra_states`.
+#[derive(Copy, Clone, Debug)]
+pub struct OutputPort {
+    pub name   : &'static str,
+    pub offset : IVec3,
+}
+
+impl OutputPort {
+    pub const fn new(name: &'static str, offset: IVec3) -> Self {
+        Self { name, offset }
+    }
 }
 
 #[derive(Copy, Clone)]
 pub struct IoPattern {
-    pub inputs : &'static [IVec3],
-    pub output : Option<IVec3>,
+    pub inputs  : &'static [IVec3],
+    pub outputs : &'static [OutputPort],
 }
 
 const SIDE_2_IN_FRONT_OUT : IoPattern = IoPattern {
     inputs : &[IVec3::NEG_X, IVec3::X],      // left & right
-    output : Some(IVec3::Z),                 // out the front
+    outputs : &[OutputPort::new("Out", IVec3::Z)],                 // out the front
+};
+
+const SIDE_2_BACK_IN_FRONT_OUT : IoPattern = IoPattern {
+    inputs : &[IVec3::NEG_X, IVec3::X, IVec3::NEG_Z], // left, right & back
+    outputs : &[OutputPort::new("Out", IVec3::Z)],
+};
+
+const SIDE_2_VERTICAL_2_IN_FRONT_OUT : IoPattern = IoPattern {
+    inputs : &[IVec3::NEG_X, IVec3::X, IVec3::Y, IVec3::NEG_Y], // left, right, above & below
+    outputs : &[OutputPort::new("Out", IVec3::Z)],
 };
 
 const BACK_1_IN_FRONT_OUT : IoPattern = IoPattern {
     inputs : &[IVec3::NEG_Z],                // single back input
-    output : Some(IVec3::Z),
+    outputs : &[OutputPort::new("Out", IVec3::Z)],
 };
 
 const FRONT_OUT : IoPattern = IoPattern {
     inputs : &[],                // single back input
-    output : Some(IVec3::Z),
+    outputs : &[OutputPort::new("Out", IVec3::Z)],
+};
+
+const BACK_OUT : IoPattern = IoPattern {
+    inputs : &[],                            // watches the cell in front
+    outputs : &[OutputPort::new("Out", IVec3::NEG_Z)],
 };
 
 const BACK_1_IN : IoPattern = IoPattern {
     inputs : &[IVec3::NEG_Z],                // single back input
-    output : Some(IVec3::ZERO),
+    outputs : &[OutputPort::new("Out", IVec3::ZERO)],
+};
+
+const DOOR_IO : IoPattern = IoPattern {
+    inputs : &[IVec3::NEG_X, IVec3::X, IVec3::NEG_Y], // either side, or the door below
+    outputs : &[OutputPort::new("Out", IVec3::ZERO)],
+};
+
+const SR_LATCH_IO : IoPattern = IoPattern {
+    inputs  : &[IVec3::NEG_X, IVec3::X],      // set & reset
+    outputs : &[
+        OutputPort::new("Q", IVec3::Z),      // out the front
+        OutputPort::new("!Q", IVec3::NEG_Z), // complement out the back
+    ],
 };
 
 const NO_IO               : IoPattern = IoPattern {
     inputs : &[],
-    output : None,
+    outputs : &[],
 };
 
 impl VoxelType {
     pub fn group(self) -> usize {
         match self {
             VoxelType::Structural(_) => 0,
-            VoxelType::BundledWire => 1, 
+            VoxelType::BundledWire
+            | VoxelType::Diode
+            | VoxelType::WireCrossing => 1, 
             VoxelType::Wire(_) => 2,
             VoxelType::Not(_) => 3,
             VoxelType::And(_) => 4,
@@ -195,6 +360,7 @@ impl VoxelType {
             VoxelType::Xor(_) => 6,
             VoxelType::Latch(_) => 7,
             VoxelType::Component(_) => 8,
+            VoxelType::Chip(_) => 8, // Kept in the component slot of the hotbar
         }
     }
     pub fn sub_group(self) -> usize {
@@ -209,6 +375,8 @@ impl VoxelType {
             VoxelType::Structural(StructuralVariants::WhiteTile) => 7,
             
             VoxelType::BundledWire => 0,
+            VoxelType::Diode => 1,
+            VoxelType::WireCrossing => 2,
             
             VoxelType::Wire(x) => x as usize,
             
@@ -217,12 +385,18 @@ impl VoxelType {
             
             VoxelType::And(AndVariants::AndGate) => 0,
             VoxelType::And(AndVariants::NandGate) => 1,
+            VoxelType::And(AndVariants::And3Gate) => 2,
+            VoxelType::And(AndVariants::And4Gate) => 3,
             
             VoxelType::Or(OrVariants::OrGate) => 0,
             VoxelType::Or(OrVariants::NorGate) => 1,
+            VoxelType::Or(OrVariants::Or3Gate) => 2,
+            VoxelType::Or(OrVariants::Or4Gate) => 3,
             
             VoxelType::Xor(XorVariants::XorGate) => 0,
             VoxelType::Xor(XorVariants::XnorGate) => 1,
+            VoxelType::Xor(XorVariants::Xor3Gate) => 2,
+            VoxelType::Xor(XorVariants::Xor4Gate) => 3,
             
             VoxelType::Latch(LatchVariants::DFlipFlop) => 0,
             VoxelType::Latch(LatchVariants::SRLatch) => 1,
@@ -231,9 +405,56 @@ impl VoxelType {
             VoxelType::Component(ComponentVariants::Switch) => 1,
             VoxelType::Component(ComponentVariants::Button) => 2,
             VoxelType::Component(ComponentVariants::Light) => 3,
+            VoxelType::Component(ComponentVariants::PressurePlate) => 4,
+            VoxelType::Component(ComponentVariants::ProximitySensor(_)) => 5,
+            VoxelType::Component(ComponentVariants::Piston) => 6,
+            VoxelType::Component(ComponentVariants::Door) => 7,
+            VoxelType::Component(ComponentVariants::UpdateDetector) => 8,
+            VoxelType::Component(ComponentVariants::Random(_)) => 9,
+            VoxelType::Component(ComponentVariants::EdgeDetector(_)) => 10,
+            VoxelType::Component(ComponentVariants::Monostable(_)) => 11,
+            VoxelType::Component(ComponentVariants::Transmitter(_)) => 12,
+            VoxelType::Component(ComponentVariants::Receiver(..)) => 13,
+            
+            VoxelType::Chip(_) => 14, // Listed in the chip inventory instead
             
         }
     }
+    /// The kind whose asset (mesh, material, texture row) this voxel uses.
+    /// Parameterised variants share the single entry listed in the voxel definitions.
+    pub fn asset_kind(self) -> VoxelType {
+        match self {
+            VoxelType::Component(ComponentVariants::ProximitySensor(_)) => {
+                VoxelType::Component(ComponentVariants::ProximitySensor(DEFAULT_PROXIMITY_RADIUS))
+            }
+            VoxelType::Component(ComponentVariants::Random(_)) => {
+                VoxelType::Component(ComponentVariants::Random(0))
+            }
+            VoxelType::Component(ComponentVariants::EdgeDetector(_)) => {
+                VoxelType::Component(ComponentVariants::EdgeDetector(EdgeMode::Rising))
+            }
+            VoxelType::Component(ComponentVariants::Monostable(_)) => {
+                VoxelType::Component(ComponentVariants::Monostable(DEFAULT_PULSE_WIDTH))
+            }
+            VoxelType::Component(ComponentVariants::Transmitter(_)) => {
+                VoxelType::Component(ComponentVariants::Transmitter(0))
+            }
+            VoxelType::Component(ComponentVariants::Receiver(..)) => {
+                VoxelType::Component(ComponentVariants::Receiver(0, false))
+            }
+            VoxelType::Chip(_) => VoxelType::Chip(ChipKind { id: 0, inputs: 0, outputs: 0 }),
+            _ => self,
+        }
+    }
+
+    /// Voxels drawn with a cable mesh instead of a full cube.
+    pub fn uses_cable_mesh(self) -> bool {
+        matches!(
+            self,
+            VoxelType::Wire(_) | VoxelType::BundledWire | VoxelType::Diode | VoxelType::WireCrossing
+        )
+    }
+
     /// Back to the legacy `(group, subtype)` pair when you need it
     pub fn as_pair(self) -> (usize, usize) {
         (self.group(), self.sub_group())
@@ -250,6 +471,17 @@ impl VoxelType {
                 Not(NotVariants::NotGate)
                 | Not(NotVariants::BufferGate)        => BACK_1_IN_FRONT_OUT,
     
+                // multi‑input gates -----------------------------------------------
+                And(AndVariants::And3Gate)
+                | Or(OrVariants::Or3Gate)
+                | Xor(XorVariants::Xor3Gate)          => SIDE_2_BACK_IN_FRONT_OUT,
+
+                And(AndVariants::And4Gate)
+                | Or(OrVariants::Or4Gate)
+                | Xor(XorVariants::Xor4Gate)          => SIDE_2_VERTICAL_2_IN_FRONT_OUT,
+
+                Latch(LatchVariants::SRLatch)         => SR_LATCH_IO,
+
                 // two‑input gates -------------------------------------------------
                 And(_) 
                 | Or(_) 
@@ -258,11 +490,31 @@ impl VoxelType {
                 
                 Component(ComponentVariants::Clock(_)) 
                 | Component(ComponentVariants::Switch)
-                | Component(ComponentVariants::Button) => FRONT_OUT,
+                | Component(ComponentVariants::Button)
+                | Component(ComponentVariants::Receiver(..))
+                | Component(ComponentVariants::PressurePlate)
+                | Component(ComponentVariants::ProximitySensor(_)) => FRONT_OUT,
+                
+                Component(ComponentVariants::Light)
+                | Component(ComponentVariants::Transmitter(_))
+                | Component(ComponentVariants::Piston) => BACK_1_IN,
                 
-                Component(ComponentVariants::Light) => BACK_1_IN,
+                Component(ComponentVariants::Door) => DOOR_IO,
+                
+                Component(ComponentVariants::UpdateDetector) => BACK_OUT,
+                
+                Component(ComponentVariants::Random(_))
+                | Component(ComponentVariants::EdgeDetector(_))
+                | Component(ComponentVariants::Monostable(_)) => BACK_1_IN_FRONT_OUT,
                 // wires are handled elsewhere ------------------------------------
                 BundledWire | Wire(_)                 => NO_IO,
+                
+                // a diode reads the cell behind it and drives the one in front
+                Diode                                 => BACK_1_IN_FRONT_OUT,
+                WireCrossing                          => NO_IO,
+
+                // pins depend on the chip, see `ChipKind`
+                Chip(_)                               => NO_IO,
             }
         }
 }
@@ -286,6 +538,8 @@ impl TryFrom<(usize, usize)> for VoxelType {
             (0,7) => VoxelType::Structural(StructuralVariants::WhiteTile),
             
             (1,0) => VoxelType::BundledWire, 
+            (1,1) => VoxelType::Diode,
+            (1,2) => VoxelType::WireCrossing,
             
             (2,0..=15) => VoxelType::Wire(s as u8),
             
@@ -294,12 +548,18 @@ impl TryFrom<(usize, usize)> for VoxelType {
             
             (4,0) => VoxelType::And(AndVariants::AndGate),
             (4,1) => VoxelType::And(AndVariants::NandGate),
+            (4,2) => VoxelType::And(AndVariants::And3Gate),
+            (4,3) => VoxelType::And(AndVariants::And4Gate),
             
             (5,0) => VoxelType::Or(OrVariants::OrGate),
             (5,1) => VoxelType::Or(OrVariants::NorGate),
+            (5,2) => VoxelType::Or(OrVariants::Or3Gate),
+            (5,3) => VoxelType::Or(OrVariants::Or4Gate),
             
             (6,0) => VoxelType::Xor(XorVariants::XorGate),
             (6,1) => VoxelType::Xor(XorVariants::XnorGate),
+            (6,2) => VoxelType::Xor(XorVariants::Xor3Gate),
+            (6,3) => VoxelType::Xor(XorVariants::Xor4Gate),
             
             (7,0) => VoxelType::Latch(LatchVariants::DFlipFlop),
             (7,1) => VoxelType::Latch(LatchVariants::SRLatch),
@@ -308,6 +568,16 @@ impl TryFrom<(usize, usize)> for VoxelType {
             (8,1) => VoxelType::Component(ComponentVariants::Switch),
             (8,2) => VoxelType::Component(ComponentVariants::Button),
             (8,3) => VoxelType::Component(ComponentVariants::Light),
+            (8,4) => VoxelType::Component(ComponentVariants::PressurePlate),
+            (8,5) => VoxelType::Component(ComponentVariants::ProximitySensor(DEFAULT_PROXIMITY_RADIUS)),
+            (8,6) => VoxelType::Component(ComponentVariants::Piston),
+            (8,7) => VoxelType::Component(ComponentVariants::Door),
+            (8,8) => VoxelType::Component(ComponentVariants::UpdateDetector),
+            (8,9) => VoxelType::Component(ComponentVariants::Random(0)),
+            (8,10) => VoxelType::Component(ComponentVariants::EdgeDetector(EdgeMode::Rising)),
+            (8,11) => VoxelType::Component(ComponentVariants::Monostable(DEFAULT_PULSE_WIDTH)),
+            (8,12) => VoxelType::Component(ComponentVariants::Transmitter(0)),
+            (8,13) => VoxelType::Component(ComponentVariants::Receiver(0, false)),
             _                => return Err("Unknown voxel_id"),
         })
     }
@@ -338,6 +608,45 @@ pub struct LoadedSaves {
 pub struct SavedWorld {
     pub world_name: String,
     pub voxels: Vec<Voxel>,
+    pub labels: Vec<VoxelLabel>,
+}
+
+/// Text attached to one face of a voxel. A label on a cable names its net.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct VoxelLabel {
+    pub position: IVec3,
+    /// Unit offset of the face the label sits on.
+    pub face: IVec3,
+    pub text: String,
+    /// Shown from every side instead of only from in front of its face.
+    pub always_visible: bool,
+}
+
+/// Build tool that currently owns the mouse buttons instead of plain placing / removing.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveTool {
+    #[default]
+    None,
+    Paste,
+    Array,
+    Line,
+    Route,
+    Chip,
+}
+
+/// Feedback of the last tool action, e.g. why a route failed, shown in the tool
+/// readout for `TOOL_MESSAGE_TIME`.
+#[derive(Resource, Default)]
+pub struct ToolMessage {
+    pub text: String,
+    pub timer: Timer,
+}
+
+impl ToolMessage {
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.timer = Timer::new(TOOL_MESSAGE_TIME, TimerMode::Once);
+    }
 }
 
 #[derive(Resource, Clone)]
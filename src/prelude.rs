@@ -18,6 +18,17 @@ pub use crate::ui::in_game::debug::*;
 pub use crate::ui::in_game::exit_menu::*;
 pub use crate::ui::in_game::speed_indicator::*;
 pub use crate::ui::in_game::clock_widget::*;
+pub use crate::ui::in_game::sensor_widget::*;
+pub use crate::ui::in_game::edge_widget::*;
+pub use crate::ui::in_game::monostable_widget::*;
+pub use crate::ui::in_game::wireless_widget::*;
+pub use crate::ui::in_game::tool_readout::*;
+pub use crate::ui::in_game::synth_widget::*;
+pub use crate::ui::in_game::chip_widget::*;
+pub use crate::ui::in_game::chip_inventory::*;
+pub use crate::ui::in_game::label_widget::*;
+pub use crate::ui::in_game::world_labels::*;
+pub use crate::ui::in_game::hover_panel::*;
 
 pub use crate::ui::main_menu::main_menu_ui::*;
 pub use crate::ui::main_menu::new_game::*;
@@ -31,6 +42,18 @@ pub use crate::voxel::voxel::*;
 pub use crate::voxel::graphics::*;
 pub use crate::voxel::helpers::*;
 pub use crate::voxel::world::*;
+pub use crate::voxel::history::*;
+
+pub use crate::tools::selection::*;
+pub use crate::tools::clipboard::*;
+pub use crate::tools::array::*;
+pub use crate::tools::symmetry::*;
+pub use crate::tools::line::*;
+pub use crate::tools::router::*;
+pub use crate::tools::synth::*;
+pub use crate::tools::chip::*;
+pub use crate::tools::label::*;
+pub use crate::tools::net_highlight::*;
 
 pub use crate::loading::loading::*;
 pub use crate::loading::save::*;
@@ -40,4 +63,8 @@ pub use crate::meta::components::*;
 pub use crate::meta::resources::*;
 
 pub use crate::simulation::logic_handler::*;
-pub use crate::simulation::graphics::*;
\ No newline at end of file
+pub use crate::simulation::graphics::*;
+pub use crate::simulation::sensors::*;
+pub use crate::simulation::actuators::*;
+pub use crate::simulation::detectors::*;
+pub use crate::simulation::chips::*;
\ No newline at end of file
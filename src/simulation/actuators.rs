@@ -0,0 +1,60 @@
+use bevy_rapier3d::prelude::Collider;
+
+use crate::prelude::*;
+
+/// Applies the world side effects of an actuator voxel switching between off and on.
+///
+/// * A `Piston` pushes the structural voxel in front of it one cell forward when it
+///   turns on, and pulls the voxel two cells ahead back against its face when it turns off.
+/// * A `Door` opens (thin panel, no collider) while powered and closes again when not.
+pub fn actuate(
+    previous: &Voxel,
+    voxel: &Voxel,
+    entity: Entity,
+    voxel_map: &mut VoxelMap,
+    save: &mut ResMut<SavedWorld>,
+    commands: &mut Commands,
+    game_events: &mut EventWriter<GameEvent>,
+) {
+    let powered = voxel.state.any_set();
+    if previous.state.any_set() == powered {
+        return;
+    }
+
+    match voxel.kind {
+        VoxelType::Component(ComponentVariants::Piston) => {
+            let front = voxel_facing(voxel);
+            let head = voxel.position + front;
+            let beyond = head + front;
+            let (from, to) = if powered { (head, beyond) } else { (beyond, head) };
+
+            let movable = voxel_map
+                .voxel_map
+                .get(&from)
+                .is_some_and(|v| matches!(v.kind, VoxelType::Structural(_)));
+            if !movable || voxel_exists(voxel_map, to) {
+                return;
+            }
+
+            move_voxel(commands, voxel_map, &mut save.labels, from, to);
+            game_events.send_batch([
+                GameEvent::UpdateMesh { updates: get_neighboring_coords(from) },
+                GameEvent::UpdateMesh { updates: get_neighboring_coords(to) },
+            ]);
+        }
+        VoxelType::Component(ComponentVariants::Door) => {
+            let animation = VoxelAnimation::new(
+                compute_voxel_transform(previous),
+                compute_voxel_transform(voxel),
+            );
+            let mut door = commands.entity(entity);
+            door.insert(animation);
+            if voxel_is_solid(voxel) {
+                door.insert(voxel_collider());
+            } else {
+                door.remove::<Collider>();
+            }
+        }
+        _ => {}
+    }
+}
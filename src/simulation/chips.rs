@@ -0,0 +1,322 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Chips inside chips inside chips ... stop being simulated past this depth.
+const MAX_CHIP_DEPTH: usize = 8;
+
+/// A wire endpoint inside a chip, brought out on one face of the chip voxel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChipPin {
+    pub name: String,
+    /// Cell of the cable inside the chip, relative to the chip's lowest corner.
+    pub position: IVec3,
+    /// Index into `CHIP_FACES`.
+    pub face: u8,
+}
+
+/// A packaged region: its voxels relative to the lowest corner and its pins,
+/// each list sorted by face so it lines up with the ports of the chip voxel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChipDefinition {
+    pub name: String,
+    pub voxels: Vec<Voxel>,
+    pub inputs: Vec<ChipPin>,
+    pub outputs: Vec<ChipPin>,
+}
+
+impl ChipDefinition {
+    pub fn id(&self) -> u32 {
+        chip_id(&self.name)
+    }
+
+    pub fn kind(&self) -> ChipKind {
+        let mask = |pins: &[ChipPin]| pins.iter().fold(0u8, |mask, pin| mask | 1 << pin.face);
+        ChipKind {
+            id: self.id(),
+            inputs: mask(&self.inputs),
+            outputs: mask(&self.outputs),
+        }
+    }
+
+    /// Whether the chip `id` is used anywhere inside this one, so saving would nest
+    /// a chip in itself.
+    pub fn contains_chip(&self, id: u32, library: &ChipLibrary) -> bool {
+        self.voxels.iter().any(|voxel| match voxel.kind {
+            VoxelType::Chip(kind) => {
+                kind.id == id
+                    || library
+                        .chips
+                        .get(&kind.id)
+                        .is_some_and(|inner| inner.contains_chip(id, library))
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Chips are identified by a hash of their name (FNV-1a), so saving a chip
+/// again under the same name, with its pins on the same faces, updates every
+/// placed copy.
+pub fn chip_id(name: &str) -> u32 {
+    name.bytes()
+        .fold(0x811C_9DC5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+/// Every chip definition, loaded from `CHIP_PATH` and kept for the whole session.
+#[derive(Resource, Default)]
+pub struct ChipLibrary {
+    pub chips: HashMap<u32, ChipDefinition>,
+}
+
+impl ChipLibrary {
+    pub fn load() -> Self {
+        let chips = load_chips()
+            .into_iter()
+            .map(|definition| (definition.id(), definition))
+            .collect();
+        Self { chips }
+    }
+
+    pub fn name(&self, id: u32) -> Option<&str> {
+        self.chips.get(&id).map(|definition| definition.name.as_str())
+    }
+
+    /// Definitions sorted by name, as listed in the chip inventory.
+    pub fn sorted(&self) -> Vec<&ChipDefinition> {
+        let mut definitions: Vec<&ChipDefinition> = self.chips.values().collect();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        definitions
+    }
+}
+
+/// Running circuits of the chips placed in the world, by position.
+///
+/// Not saved: after loading, every chip starts from the states it was packaged with.
+#[derive(Resource, Default)]
+pub struct ChipInstances {
+    pub chips: HashMap<IVec3, ChipState>,
+}
+
+/// The circuit inside one placed chip.
+pub struct ChipState {
+    pub id: u32,
+    map: VoxelMap,
+    memory: GateMemory,
+    chips: HashMap<IVec3, ChipState>,
+    /// Hidden switches feeding the input pins, and whether the pin is a single-channel wire.
+    drivers: Vec<(IVec3, bool)>,
+    /// Output pin cells, and whether the pin is a single-channel wire.
+    outputs: Vec<(IVec3, bool)>,
+}
+
+impl ChipState {
+    pub fn new(definition: &ChipDefinition) -> Result<Self, String> {
+        let voxel_map: HashMap<IVec3, Voxel> = definition
+            .voxels
+            .iter()
+            .map(|voxel| (voxel.position, *voxel))
+            .collect();
+        let mut map = VoxelMap {
+            entity_map: HashMap::new(),
+            voxel_map,
+            asset_map: HashMap::new(),
+            generation: 0,
+        };
+
+        let is_wire = |map: &VoxelMap, pin: &ChipPin| {
+            matches!(map.voxel_map.get(&pin.position).map(|v| v.kind), Some(VoxelType::Wire(_)))
+        };
+        let mut drivers = Vec::new();
+        for pin in &definition.inputs {
+            let cell = driver_cell(&map, pin.position)
+                .ok_or_else(|| format!("input {} has no free side to be driven from", pin.name))?;
+            let direction = facing_direction(pin.position - cell);
+            let switch = fresh_voxel(VoxelType::Component(ComponentVariants::Switch), cell, direction);
+            drivers.push((cell, is_wire(&map, pin)));
+            map.voxel_map.insert(cell, switch);
+        }
+        let outputs = definition
+            .outputs
+            .iter()
+            .map(|pin| (pin.position, is_wire(&map, pin)))
+            .collect();
+
+        Ok(Self {
+            id: definition.id(),
+            map,
+            memory: GateMemory::default(),
+            chips: HashMap::new(),
+            drivers,
+            outputs,
+        })
+    }
+
+    /// Feeds one word per input pin, runs one tick and returns one word per output pin.
+    fn step(
+        &mut self,
+        inputs: &[Bits16],
+        sim_timer: &mut SimulationTimer,
+        library: &ChipLibrary,
+        depth: usize,
+    ) -> Vec<Bits16> {
+        for (&(cell, single), word) in self.drivers.iter().zip(inputs) {
+            if let Some(switch) = self.map.voxel_map.get_mut(&cell) {
+                switch.state = if single { bitword(word.any_set()) } else { *word };
+            }
+        }
+
+        let events = simulate_tick(
+            &self.map,
+            sim_timer,
+            &HashSet::new(),
+            &mut self.memory,
+            &mut self.chips,
+            library,
+            depth,
+        );
+        for event in events {
+            match event {
+                LogicEvent::UpdateVoxel { position, new_state } => {
+                    if let Some(voxel) = self.map.voxel_map.get_mut(&position) {
+                        voxel.state = new_state;
+                    }
+                }
+                LogicEvent::UpdatePort { position, port, new_state } => {
+                    if let Some(voxel) = self.map.voxel_map.get_mut(&position) {
+                        voxel.set_port_state(port, new_state);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // A single-channel pin drives every channel outside, like a gate
+        self.outputs
+            .iter()
+            .map(|&(cell, single)| {
+                let word = self.map.voxel_map.get(&cell).map_or(Bits16::all_zeros(), |v| v.state);
+                if single { bitword(word.any_set()) } else { word }
+            })
+            .collect()
+    }
+}
+
+/// Empty cell next to `pin` where a hidden switch can drive it without feeding
+/// any other port of the circuit.
+fn driver_cell(map: &VoxelMap, pin: IVec3) -> Option<IVec3> {
+    get_neighboring_coords(pin).into_iter().find(|&cell| {
+        !map.voxel_map.contains_key(&cell)
+            && get_neighboring_coords(cell).iter().all(|neighbor| {
+                *neighbor == pin
+                    || map.voxel_map.get(neighbor).is_none_or(|voxel| {
+                        let (inputs, outputs) = voxel_directions(voxel);
+                        !inputs.contains(&cell) && !outputs.contains(&cell)
+                    })
+            })
+    })
+}
+
+/// Direction that makes a voxel face along the unit offset `dir`.
+fn facing_direction(dir: IVec3) -> u8 {
+    match (dir.x, dir.y, dir.z) {
+        (1, 0, 0) => 2,
+        (0, 0, -1) => 3,
+        (-1, 0, 0) => 4,
+        (0, 1, 0) => 5,
+        (0, -1, 0) => 9,
+        _ => 1,
+    }
+}
+
+/// Words on the output ports of a placed chip after one tick of its circuit.
+/// Chips without a definition, or nested too deep, output nothing.
+pub fn simulate_chip(
+    voxel: &Voxel,
+    kind: ChipKind,
+    voxel_map: &VoxelMap,
+    sim_timer: &mut SimulationTimer,
+    chips: &mut HashMap<IVec3, ChipState>,
+    library: &ChipLibrary,
+    depth: usize,
+) -> Vec<Bits16> {
+    let (inputs, outputs) = voxel_directions(voxel);
+    let idle = vec![Bits16::all_zeros(); outputs.len()];
+    let Some(definition) = library.chips.get(&kind.id) else {
+        return idle;
+    };
+    if depth >= MAX_CHIP_DEPTH {
+        return idle;
+    }
+
+    let state = match chips.entry(voxel.position) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => match ChipState::new(definition) {
+            Ok(state) => entry.insert(state),
+            Err(_) => return idle,
+        },
+    };
+    let words: Vec<Bits16> = inputs
+        .iter()
+        .map(|&cell| input_word(voxel_map, voxel.position, cell))
+        .collect();
+    state.step(&words, sim_timer, library, depth + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Not gate between an input wire on the -X face and an output wire on the +Z face.
+    fn inverter() -> ChipDefinition {
+        ChipDefinition {
+            name: "inverter".to_string(),
+            voxels: vec![
+                fresh_voxel(VoxelType::Wire(0), IVec3::ZERO, 1),
+                fresh_voxel(VoxelType::Not(NotVariants::NotGate), IVec3::new(0, 0, 1), 1),
+                fresh_voxel(VoxelType::Wire(0), IVec3::new(0, 0, 2), 1),
+            ],
+            inputs: vec![ChipPin { name: "a".to_string(), position: IVec3::ZERO, face: 0 }],
+            outputs: vec![ChipPin { name: "y".to_string(), position: IVec3::new(0, 0, 2), face: 3 }],
+        }
+    }
+
+    #[test]
+    fn placed_chip_runs_its_saved_circuit() {
+        // Encoded and decoded the way `save_chip` / `load_chips` do
+        let config = bincode::config::standard();
+        let bytes = bincode::serde::encode_to_vec(inverter(), config).unwrap();
+        let (definition, _): (ChipDefinition, usize) = bincode::serde::decode_from_slice(&bytes, config).unwrap();
+        let kind = definition.kind();
+        let library = ChipLibrary {
+            chips: HashMap::from([(kind.id, definition)]),
+        };
+
+        let chip = fresh_voxel(VoxelType::Chip(kind), IVec3::ZERO, 1);
+        let switch = fresh_voxel(VoxelType::Component(ComponentVariants::Switch), IVec3::NEG_X, 2);
+        let mut voxel_map = VoxelMap {
+            entity_map: HashMap::new(),
+            voxel_map: HashMap::from([(chip.position, chip), (switch.position, switch)]),
+            asset_map: HashMap::new(),
+            generation: 0,
+        };
+        let mut sim_timer = SimulationTimer {
+            tick: Timer::from_seconds(1.0, TimerMode::Repeating),
+            rate: 1,
+            tick_counter: 0,
+        };
+        let mut chips = HashMap::new();
+        let mut settle = |voxel_map: &VoxelMap| {
+            (0..4)
+                .map(|_| simulate_chip(&chip, kind, voxel_map, &mut sim_timer, &mut chips, &library, 0))
+                .last()
+                .unwrap()
+        };
+
+        assert_eq!(settle(&voxel_map), [bitword(true)]);
+        voxel_map.voxel_map.get_mut(&IVec3::NEG_X).unwrap().state = bitword(true);
+        assert_eq!(settle(&voxel_map), [bitword(false)]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use crate::prelude::*;
+
+/// Positions whose voxel was placed, removed or changed state since the last tick.
+///
+/// Filled by `block_update_listener` and drained by `logic_system`, so every
+/// update is seen by the detectors exactly once, on the tick that follows it.
+#[derive(Resource, Default)]
+pub struct BlockUpdates {
+    pub positions: HashSet<IVec3>,
+}
+
+/// Records world edits and state changes for the `UpdateDetector` voxels.
+///
+/// Runs after every `EditSenders` system and before `event_handler` /
+/// `logic_event_handler`, so it reads each event in the frame it is sent while
+/// the voxel map still holds the previous contents: placing into an occupied
+/// cell, removing an empty one or re-sending an unchanged state is not counted
+/// as an update.
+pub fn block_update_listener(
+    mut game_events: EventReader<GameEvent>,
+    mut logic_events: EventReader<LogicEvent>,
+    voxel_map: Res<VoxelMap>,
+    mut block_updates: ResMut<BlockUpdates>,
+) {
+    for event in game_events.read() {
+        match event {
+            GameEvent::PlaceBlock { voxel, .. } if !voxel_map.voxel_map.contains_key(&voxel.position) => {
+                block_updates.positions.insert(voxel.position);
+            }
+            GameEvent::RemoveBlock { position } if voxel_map.voxel_map.contains_key(position) => {
+                block_updates.positions.insert(*position);
+            }
+            _ => {}
+        }
+    }
+
+    for event in logic_events.read() {
+        let (position, port, new_state) = match event {
+            LogicEvent::UpdateVoxel { position, new_state } => (position, 0, new_state),
+            LogicEvent::UpdatePort { position, port, new_state } => (position, *port, new_state),
+            _ => continue,
+        };
+        let changed = voxel_map
+            .voxel_map
+            .get(position)
+            .is_some_and(|voxel| voxel.port_state(port) != *new_state);
+        if changed {
+            block_updates.positions.insert(*position);
+        }
+    }
+}
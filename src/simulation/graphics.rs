@@ -1,8 +1,59 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
 use crate::prelude::*;
 
+/// Emissive strength of a lit wire channel.
+const WIRE_GLOW: f32 = 0.6;
+/// Pixels per atlas face, one stripe per channel across each face.
+const STRIPE_FACE_WIDTH: u32 = 16;
+const STRIPE_FACES: u32 = 6;
+
+/// Glow colour of `channel`. Neighbouring channels sit far apart on the colour
+/// wheel so adjacent wires are easy to tell apart.
+pub fn channel_color(channel: u8) -> LinearRgba {
+    let hue = (channel as u32 * 7 % 16) as f32 * 360.0 / 16.0;
+    Color::hsl(hue, 0.9, 0.55).to_linear()
+}
+
+/// Emissive texture for a bundled wire carrying `word`: one stripe per channel
+/// on every face of the atlas layout, coloured where the channel is high.
+fn bundle_stripes(word: Bits16) -> Image {
+    let mut data = Vec::with_capacity((STRIPE_FACE_WIDTH * STRIPE_FACES * 4) as usize);
+    for _ in 0..STRIPE_FACES {
+        for channel in 0..STRIPE_FACE_WIDTH as u8 {
+            let color = if word.get(channel) {
+                Color::from(channel_color(channel)).to_srgba().to_u8_array()
+            } else {
+                [0, 0, 0, 255]
+            };
+            data.extend_from_slice(&color);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: STRIPE_FACE_WIDTH * STRIPE_FACES,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Lights voxels according to their state. A wire glows in the colour of its
+/// channel, a bundled wire shows a coloured stripe for each high channel.
 pub fn update_emissive(
     graphics_query: Query<(&Voxel, &MeshMaterial3d<StandardMaterial>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut stripe_textures: Local<HashMap<u16, Handle<Image>>>,
 ) {
     for (voxel, material_handle) in graphics_query.iter() {
         if Bits16::any_set(voxel.state) {
@@ -11,6 +62,18 @@ pub fn update_emissive(
                     VoxelType::Component(ComponentVariants::Light) => {
                         material.emissive = LinearRgba::new(0.8, 0.8, 0.8, 0.8);
                     }
+                    VoxelType::Wire(channel) => {
+                        material.emissive = channel_color(channel) * WIRE_GLOW;
+                    }
+                    VoxelType::BundledWire => {
+                        // One texture per combination of high channels, shared by every bundle
+                        let stripes = stripe_textures
+                            .entry(voxel.state.value())
+                            .or_insert_with(|| images.add(bundle_stripes(voxel.state)))
+                            .clone();
+                        material.emissive = LinearRgba::WHITE * WIRE_GLOW;
+                        material.emissive_texture = Some(stripes);
+                    }
                     _ => {
                         material.emissive = LinearRgba::new(0.0, 0.1, 0.0, 0.2);
                     }
@@ -19,6 +82,7 @@ pub fn update_emissive(
         } else {
             if let Some(material) = materials.get_mut(&material_handle.0) {
                 material.emissive = LinearRgba::new(0.0, 0.0, 0.0, 1.0);
+                material.emissive_texture = None;
             }
         }
     }
@@ -1,12 +1,15 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::prelude::*; 
 
 /// Returns `true` when `voxel` is capable of transporting **`channel`**.
 #[inline(always)]
 fn carries(voxel: &Voxel, channel: u8) -> bool {
     match voxel.kind {
-        VoxelType::Wire(ch)    => ch == channel,
-        VoxelType::BundledWire => true,
-        _                      => false,
+        VoxelType::Wire(ch)     => ch == channel,
+        VoxelType::BundledWire  => true,
+        VoxelType::WireCrossing => true,
+        _                       => false,
     }
 }
 
@@ -33,6 +36,29 @@ pub struct SimulationTimer {
     pub tick_counter: u8,
 }
 
+/// Per-voxel memory for gates that react to input edges rather than levels.
+///
+/// Not saved: after loading, every input starts out as "unchanged".
+#[derive(Resource, Default)]
+pub struct GateMemory {
+    pub last_input: HashMap<IVec3, bool>,
+    pub pulse_ticks: HashMap<IVec3, usize>, // Remaining high ticks of a monostable
+}
+
+impl GateMemory {
+    /// Stores `input` for `position` and reports whether it just went high.
+    pub fn rising(&mut self, position: IVec3, input: bool) -> bool {
+        let previous = self.last_input.insert(position, input).unwrap_or(input);
+        input && !previous
+    }
+
+    /// Stores `input` for `position` and reports whether it just changed.
+    pub fn changed(&mut self, position: IVec3, input: bool) -> bool {
+        let previous = self.last_input.insert(position, input).unwrap_or(input);
+        input != previous
+    }
+}
+
 #[derive(Event, Debug)]
 pub enum LogicEvent {
     Skip, // Event flag that skips the current iteration of the event handler
@@ -43,13 +69,40 @@ pub enum LogicEvent {
     UpdateClockVoxel {
         position: IVec3,
         new_speed: usize,
-    }
+    },
+    UpdateSensorVoxel {
+        position: IVec3,
+        new_radius: usize,
+    },
+    UpdateEdgeVoxel {
+        position: IVec3,
+        new_mode: EdgeMode,
+    },
+    UpdatePulseVoxel {
+        position: IVec3,
+        new_width: usize,
+    },
+    UpdateWirelessVoxel {
+        position: IVec3,
+        new_frequency: usize,
+    },
+    UpdateReceiverDelay {
+        position: IVec3,
+        delayed: bool,
+    },
+    UpdatePort {
+        position: IVec3,
+        port: usize,
+        new_state: Bits16,
+    },
 }
 
 pub fn logic_event_handler(
     mut logic_events: EventReader<LogicEvent>,
     mut voxel_map: ResMut<VoxelMap>,
+    mut save: ResMut<SavedWorld>,
     mut commands: Commands,
+    mut game_events: EventWriter<GameEvent>,
 ) {
     
     for event in logic_events.read() {
@@ -58,23 +111,87 @@ pub fn logic_event_handler(
                 // No-op for skip events.
             }
             LogicEvent::UpdateVoxel { position, new_state } => {
-                if let Some(entity) = voxel_map.entity_map.get(&position).cloned() {
-                    if let Some(voxel) = voxel_map.voxel_map.get_mut(&position) {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
                         if voxel.state != *new_state {
+                            let previous = *voxel;
                             voxel.state = *new_state;
-                            commands.entity(entity).insert(voxel.clone());
+                            commands.entity(entity).insert(*voxel);
+
+                            let updated = *voxel;
+                            actuate(&previous, &updated, entity, &mut voxel_map, &mut save, &mut commands, &mut game_events);
+                        }
+                    }
+                }
+            }
+            LogicEvent::UpdatePort { position, port, new_state } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        if voxel.port_state(*port) != *new_state {
+                            voxel.set_port_state(*port, *new_state);
+                            commands.entity(entity).insert(*voxel);
                         }
                     }
                 }
             }
             LogicEvent::UpdateClockVoxel { position, new_speed } => {
-                if let Some(entity) = voxel_map.entity_map.get(&position).cloned() {
-                    if let Some(voxel) = voxel_map.voxel_map.get_mut(&position) {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
                         voxel.kind = VoxelType::Component(ComponentVariants::Clock(*new_speed));
-                        commands.entity(entity).insert(voxel.clone());
+                        commands.entity(entity).insert(*voxel);
                     }
                 }
             } 
+            LogicEvent::UpdateSensorVoxel { position, new_radius } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        voxel.kind = VoxelType::Component(ComponentVariants::ProximitySensor(*new_radius));
+                        commands.entity(entity).insert(*voxel);
+                    }
+                }
+            }
+            LogicEvent::UpdateEdgeVoxel { position, new_mode } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        voxel.kind = VoxelType::Component(ComponentVariants::EdgeDetector(*new_mode));
+                        commands.entity(entity).insert(*voxel);
+                    }
+                }
+            }
+            LogicEvent::UpdatePulseVoxel { position, new_width } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        voxel.kind = VoxelType::Component(ComponentVariants::Monostable(*new_width));
+                        commands.entity(entity).insert(*voxel);
+                    }
+                }
+            }
+            LogicEvent::UpdateWirelessVoxel { position, new_frequency } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        voxel.kind = match voxel.kind {
+                            VoxelType::Component(ComponentVariants::Transmitter(_)) => {
+                                VoxelType::Component(ComponentVariants::Transmitter(*new_frequency))
+                            }
+                            VoxelType::Component(ComponentVariants::Receiver(_, delayed)) => {
+                                VoxelType::Component(ComponentVariants::Receiver(*new_frequency, delayed))
+                            }
+                            kind => kind,
+                        };
+                        commands.entity(entity).insert(*voxel);
+                    }
+                }
+            }
+            LogicEvent::UpdateReceiverDelay { position, delayed } => {
+                if let Some(entity) = voxel_map.entity_map.get(position).cloned() {
+                    if let Some(voxel) = voxel_map.voxel_map.get_mut(position) {
+                        if let VoxelType::Component(ComponentVariants::Receiver(frequency, _)) = voxel.kind {
+                            voxel.kind = VoxelType::Component(ComponentVariants::Receiver(frequency, *delayed));
+                            commands.entity(entity).insert(*voxel);
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -86,134 +203,412 @@ pub fn logic_system(
     time: Res<Time>,
     mut sim_timer: ResMut<SimulationTimer>,
     voxel_map: ResMut<VoxelMap>,
+    mut block_updates: ResMut<BlockUpdates>,
+    mut gate_memory: ResMut<GateMemory>,
+    mut chip_instances: ResMut<ChipInstances>,
+    chip_library: Res<ChipLibrary>,
     mut logic_writer: EventWriter<LogicEvent>,
 ) { 
     // advance the clock
     sim_timer.tick.tick(time.delta());
     if !sim_timer.tick.finished() { return; }
     sim_timer.tick_counter += 1;
+
+    // everything that changed since the previous tick
+    let updates = std::mem::take(&mut block_updates.positions);
+
+    let events = simulate_tick(
+        &voxel_map,
+        &mut sim_timer,
+        &updates,
+        &mut gate_memory,
+        &mut chip_instances.chips,
+        &chip_library,
+        0,
+    );
+    for event in events {
+        logic_writer.send(event);
+    }
+}
+
+/// One step of the simulation over `voxel_map`, computed from the states of the
+/// previous step. Also runs the circuits inside custom chips, `depth` levels deep.
+pub fn simulate_tick(
+    voxel_map: &VoxelMap,
+    sim_timer: &mut SimulationTimer,
+    updates: &HashSet<IVec3>,
+    gate_memory: &mut GateMemory,
+    chips: &mut HashMap<IVec3, ChipState>,
+    chip_library: &ChipLibrary,
+    depth: usize,
+) -> Vec<LogicEvent> {
+    let mut events = Vec::new();
+
+    // forget removed gates so a block placed in their spot starts clean
+    gate_memory.last_input.retain(|pos, _| voxel_map.voxel_map.contains_key(pos));
+    gate_memory.pulse_ticks.retain(|pos, _| voxel_map.voxel_map.contains_key(pos));
+    chips.retain(|pos, chip| {
+        matches!(voxel_map.voxel_map.get(pos).map(|v| v.kind), Some(VoxelType::Chip(kind)) if kind.id == chip.id)
+    });
     
     // ── A. Re‑simulate *every* gate ─────────────────────────────────────────
     for (&pos, voxel) in voxel_map.voxel_map.iter() {
-        if let Some(new_state) = simulate_gate(voxel, &voxel_map, &mut sim_timer) {
-            logic_writer.send(LogicEvent::UpdateVoxel {
+        if let VoxelType::Chip(kind) = voxel.kind {
+            let outputs = simulate_chip(voxel, kind, voxel_map, sim_timer, chips, chip_library, depth);
+            for (port, word) in outputs.into_iter().enumerate() {
+                if word != voxel.port_state(port) {
+                    events.push(LogicEvent::UpdatePort { position: pos, port, new_state: word });
+                }
+            }
+            continue;
+        }
+
+        let new_state = simulate_gate(voxel, voxel_map, sim_timer, updates, gate_memory);
+        if let Some(new_state) = new_state {
+            events.push(LogicEvent::UpdateVoxel {
                 position: pos,
                 new_state,
             });
 
             // if the gate drives a plain wire, update that wire immediately
-            let (_, output) = voxel_directions(voxel);
-            if let Some(out_voxel) = voxel_map.voxel_map.get(&output) {
-                if matches!(out_voxel.kind, VoxelType::Wire(_)) {
-                    logic_writer.send(LogicEvent::UpdateVoxel {
-                        position: output,
-                        new_state,
-                    });
+            let (_, outputs) = voxel_directions(voxel);
+            if let Some(&output) = outputs.first() {
+                if let Some(out_voxel) = voxel_map.voxel_map.get(&output) {
+                    if matches!(out_voxel.kind, VoxelType::Wire(_)) {
+                        events.push(LogicEvent::UpdateVoxel {
+                            position: output,
+                            new_state,
+                        });
+                    }
                 }
             }
         }
+
+        // the remaining output ports follow port 0
+        for (port, word) in extra_port_states(voxel, new_state.unwrap_or(voxel.state)) {
+            if word != voxel.port_state(port) {
+                events.push(LogicEvent::UpdatePort {
+                    position: pos,
+                    port,
+                    new_state: word,
+                });
+            }
+        }
     }
 
     // ── B. Re‑propagate *all* wires ─────────────────────────────────────────
-    for event in propagate_wires(&voxel_map) {
-        logic_writer.send(event);
+    events.extend(propagate_wires(voxel_map));
+
+    // ── C. Broadcast every wireless frequency ───────────────────────────────
+    events.extend(propagate_wireless(voxel_map));
+
+    events
+}
+
+/// Wireless nets: every receiver outputs the OR of all transmitters on its frequency.
+///
+/// Transmitters latch the word behind them into their own state. Instant receivers
+/// read those input words directly; delayed receivers read the latched states, which
+/// lag one tick behind.
+pub fn propagate_wireless(voxel_map: &VoxelMap) -> Vec<LogicEvent> {
+    let mut events = Vec::new();
+    let mut live: HashMap<usize, u16> = HashMap::new();
+    let mut latched: HashMap<usize, u16> = HashMap::new();
+
+    // --- 1. gather every transmitter per frequency --------------------------
+    for (&pos, v) in &voxel_map.voxel_map {
+        let VoxelType::Component(ComponentVariants::Transmitter(frequency)) = v.kind else {
+            continue;
+        };
+        let (ins, _) = voxel_directions(v);
+        let input = ins
+            .first()
+            .map_or(Bits16::all_zeros(), |&p| input_word(voxel_map, pos, p));
+
+        *live.entry(frequency).or_default() |= input.value();
+        *latched.entry(frequency).or_default() |= v.state.value();
+
+        if input != v.state {
+            events.push(LogicEvent::UpdateVoxel { position: pos, new_state: input });
+        }
+    }
+
+    // --- 2. drive the receivers --------------------------------------------
+    for (&pos, v) in &voxel_map.voxel_map {
+        let VoxelType::Component(ComponentVariants::Receiver(frequency, delayed)) = v.kind else {
+            continue;
+        };
+        let net = if delayed { &latched } else { &live };
+        let word = Bits16::new(net.get(&frequency).copied().unwrap_or(0));
+
+        if word != v.state {
+            events.push(LogicEvent::UpdateVoxel { position: pos, new_state: word });
+        }
     }
+
+    events
 }
 
 
 
+/// A node of a wire net: a whole cell, or one of the two runs through a crossing.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Lane {
+    Cell,
+    X,
+    Z,
+}
+
+/// The lane a signal lands in when it enters `pos` travelling along `dir`.
+/// Crossings can only be entered horizontally, along one of their runs.
+fn lane_into(voxel_map: &VoxelMap, pos: IVec3, dir: IVec3) -> Option<Lane> {
+    match voxel_map.voxel_map.get(&pos).map(|v| v.kind) {
+        Some(VoxelType::WireCrossing) => {
+            if dir.x != 0 && dir.y == 0 && dir.z == 0 {
+                Some(Lane::X)
+            } else if dir.z != 0 && dir.x == 0 && dir.y == 0 {
+                Some(Lane::Z)
+            } else {
+                None
+            }
+        }
+        _ => Some(Lane::Cell),
+    }
+}
+
+/// Calls `visit` with every carrier of channel `ch` that a signal in `lane` of
+/// `cur` spreads into, and the lane it lands in. Insulated faces and crossings
+/// are respected.
+fn for_each_net_neighbor(
+    voxel_map: &VoxelMap,
+    cur: IVec3,
+    lane: Lane,
+    ch: u8,
+    mut visit: impl FnMut((IVec3, Lane)),
+) {
+    let dirs = [
+        IVec3::new( 1, 0, 0), IVec3::new(-1, 0, 0),
+        IVec3::new( 0, 1, 0), IVec3::new( 0,-1, 0),
+        IVec3::new( 0, 0, 1), IVec3::new( 0, 0,-1),
+    ];
+
+    for d in dirs {
+        let along_lane = match lane {
+            Lane::Cell => true,
+            Lane::X    => d.x != 0,
+            Lane::Z    => d.z != 0,
+        };
+        if !along_lane {
+            continue;
+        }
+        let nb = cur + d;
+        let Some(nb_voxel) = voxel_map.voxel_map.get(&nb) else { continue };
+        if !carries(nb_voxel, ch) {
+            continue;
+        }
+        if voxel_map.voxel_map[&cur].is_insulated(d) || nb_voxel.is_insulated(-d) {
+            continue;
+        }
+        let Some(nb_lane) = lane_into(voxel_map, nb, d) else { continue };
+        visit((nb, nb_lane));
+    }
+}
+
+/// Cells of the channel `ch` net running through `start`, connected the same
+/// way `propagate_wires` spreads a signal (diodes are not followed). Empty when
+/// `start` doesn't carry `ch`.
+pub fn wire_net(voxel_map: &VoxelMap, start: IVec3, ch: u8) -> HashSet<IVec3> {
+    let start_lanes: &[Lane] = match voxel_map.voxel_map.get(&start) {
+        Some(v) if !carries(v, ch) => &[],
+        Some(v) if v.kind == VoxelType::WireCrossing => &[Lane::X, Lane::Z],
+        Some(_) => &[Lane::Cell],
+        None => &[],
+    };
+
+    let mut seen: HashSet<(IVec3, Lane)> = start_lanes.iter().map(|&lane| (start, lane)).collect();
+    let mut stack: Vec<(IVec3, Lane)> = seen.iter().copied().collect();
+    while let Some((cur, lane)) = stack.pop() {
+        for_each_net_neighbor(voxel_map, cur, lane, ch, |node| {
+            if seen.insert(node) {
+                stack.push(node);
+            }
+        });
+    }
+    seen.into_iter().map(|(cell, _)| cell).collect()
+}
+
 pub fn propagate_wires(voxel_map: &VoxelMap) -> Vec<LogicEvent> {
-    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::collections::{hash_map::Entry, HashMap, VecDeque};
 
     // --- 1. gather every *gate* output word ---------------------------------
-    let mut gate_drive: HashMap<IVec3, Bits16> = HashMap::new();
+    let mut gate_drive: HashMap<(IVec3, Lane), Bits16> = HashMap::new();
 
     for (_, v) in &voxel_map.voxel_map {
-        if matches!(v.kind, VoxelType::Wire(_) | VoxelType::BundledWire) {
+        if matches!(
+            v.kind,
+            VoxelType::Wire(_) | VoxelType::BundledWire | VoxelType::Diode | VoxelType::WireCrossing
+        ) {
             continue;                       // skip cables themselves
         }
-        if v.state.any_set() {
-            let (_, out_pos) = voxel_directions(v);
+        let (_, outputs) = voxel_directions(v);
+        for (port, out_pos) in outputs.into_iter().enumerate() {
+            let word = v.port_state(port);
+            if !word.any_set() {
+                continue;
+            }
+            let Some(lane) = lane_into(voxel_map, out_pos, out_pos - v.position) else {
+                continue;
+            };
+            if voxel_map.voxel_map.get(&out_pos).is_some_and(|o| o.is_insulated(v.position - out_pos)) {
+                continue;                   // the cable turned that face away
+            }
             gate_drive
-                .entry(out_pos)
-                .and_modify(|w| *w = Bits16::new(w.value() | v.state.value()))
-                .or_insert(v.state);
+                .entry((out_pos, lane))
+                .and_modify(|w| *w = Bits16::new(w.value() | word.value()))
+                .or_insert(word);
         }
     }
 
-    // --- 2. flood‑fill *per channel* ----------------------------------------
-    let dirs = [
-        IVec3::new( 1, 0, 0), IVec3::new(-1, 0, 0),
-        IVec3::new( 0, 1, 0), IVec3::new( 0,-1, 0),
-        IVec3::new( 0, 0, 1), IVec3::new( 0, 0,-1),
-    ];
+    let diodes: Vec<&Voxel> = voxel_map
+        .voxel_map
+        .values()
+        .filter(|v| v.kind == VoxelType::Diode)
+        .collect();
 
+    // --- 2. flood‑fill *per channel* ----------------------------------------
     // we collect all pending edits first, so a BundledWire can get
     // several bits flipped in the same tick without races
     let mut pending: HashMap<IVec3, Bits16> = HashMap::new();
 
     // channels are 1‑based in your API
     for ch in 0..16u8 {
-        let mut visited: HashSet<IVec3> = HashSet::new();
+        let mut net_of: HashMap<(IVec3, Lane), usize> = HashMap::new();
+        let mut nets: Vec<Vec<(IVec3, Lane)>> = Vec::new();
 
         for (&start_pos, start_v) in &voxel_map.voxel_map {
-            if visited.contains(&start_pos) || !carries(start_v, ch) {
+            if !carries(start_v, ch) {
                 continue;
             }
+            let start_lanes: &[Lane] = if start_v.kind == VoxelType::WireCrossing {
+                &[Lane::X, Lane::Z]
+            } else {
+                &[Lane::Cell]
+            };
 
-            // ----- breadth‑first search over carriers of *this* channel -----
-            let mut queue     = VecDeque::new();
-            let mut component = Vec::new();
+            for &start_lane in start_lanes {
+                if net_of.contains_key(&(start_pos, start_lane)) {
+                    continue;
+                }
 
-            visited.insert(start_pos);
-            queue.push_back(start_pos);
+                // ----- breadth‑first search over carriers of *this* channel -
+                let id = nets.len();
+                let mut queue     = VecDeque::new();
+                let mut component = Vec::new();
 
-            while let Some(cur) = queue.pop_front() {
-                component.push(cur);
+                net_of.insert((start_pos, start_lane), id);
+                queue.push_back((start_pos, start_lane));
 
-                for &d in &dirs {
-                    let nb = cur + d;
-                    if visited.contains(&nb) {
-                        continue;
-                    }
-                    if let Some(nb_voxel) = voxel_map.voxel_map.get(&nb) {
-                        if carries(nb_voxel, ch) {
-                            visited.insert(nb);
-                            queue.push_back(nb);
+                while let Some((cur, lane)) = queue.pop_front() {
+                    component.push((cur, lane));
+
+                    for_each_net_neighbor(voxel_map, cur, lane, ch, |node| {
+                        if let Entry::Vacant(entry) = net_of.entry(node) {
+                            entry.insert(id);
+                            queue.push_back(node);
                         }
+                    });
+                }
+
+                nets.push(component);
+            }
+        }
+
+        // ----- every diode is a net of its own, fed only from behind -------
+        for diode in &diodes {
+            net_of.insert((diode.position, Lane::Cell), nets.len());
+            nets.push(vec![(diode.position, Lane::Cell)]);
+        }
+
+        let mut feeds: Vec<Vec<usize>> = vec![Vec::new(); nets.len()];
+        for diode in &diodes {
+            let facing = voxel_facing(diode);
+            let own = net_of[&(diode.position, Lane::Cell)];
+
+            // behind: a carrier, or a diode pointing into this one
+            let back = diode.position - facing;
+            if voxel_map.voxel_map.get(&back).is_some_and(|v| carries(v, ch) && !v.is_insulated(facing)) {
+                if let Some(lane) = lane_into(voxel_map, back, facing) {
+                    if let Some(&from) = net_of.get(&(back, lane)) {
+                        feeds[from].push(own);
                     }
                 }
             }
 
-            // ----- does anything in this blob *want* the bit on? ------------
-            let driven_high = component.iter().any(|&p| {
-                gate_drive
-                    .get(&p)
-                    .map_or(false, |w| w.get(ch))
-            });
+            // in front: a carrier, or a diode facing the same way
+            let front = diode.position + facing;
+            let Some(front_voxel) = voxel_map.voxel_map.get(&front) else { continue };
+            let accepts = if front_voxel.kind == VoxelType::Diode {
+                front - voxel_facing(front_voxel) == diode.position
+            } else {
+                carries(front_voxel, ch) && !front_voxel.is_insulated(-facing)
+            };
+            if accepts {
+                if let Some(lane) = lane_into(voxel_map, front, facing) {
+                    if let Some(&to) = net_of.get(&(front, lane)) {
+                        feeds[own].push(to);
+                    }
+                }
+            }
+        }
 
-            // ----- schedule updates where the bit differs -------------------
-            for &p in &component {
-                let cur_word = voxel_map.voxel_map[&p].state;
-                let bit_is_on = cur_word.get(ch);
-
-                if bit_is_on != driven_high {
-                    pending
-                        .entry(p)
-                        .and_modify(|w| {
-                            if driven_high { w.set(ch) } else { w.clear(ch) }
-                            *w = clamp_state(&voxel_map.voxel_map[&p].kind, *w);
-                        })
-                        
-                        // inside or_insert_with
-                        .or_insert_with(|| {
-                            let mut w = cur_word;
-                            if driven_high { w.set(ch) } else { w.clear(ch) }
-                            clamp_state(&voxel_map.voxel_map[&p].kind, w)
-                        });
+        // ----- does anything in this blob *want* the bit on? ----------------
+        let mut driven_high: Vec<bool> = nets
+            .iter()
+            .map(|net| {
+                net.iter().any(|node| gate_drive.get(node).is_some_and(|w| w.get(ch)))
+            })
+            .collect();
+
+        // ----- push high nets through the diodes ----------------------------
+        let mut queue: VecDeque<usize> = (0..nets.len()).filter(|&id| driven_high[id]).collect();
+        while let Some(id) = queue.pop_front() {
+            for &to in &feeds[id] {
+                if !driven_high[to] {
+                    driven_high[to] = true;
+                    queue.push_back(to);
                 }
             }
         }
+
+        // a crossing shows the bit when either of its runs carries it
+        let mut cell_high: HashMap<IVec3, bool> = HashMap::new();
+        for (id, net) in nets.iter().enumerate() {
+            for &(p, _) in net {
+                *cell_high.entry(p).or_default() |= driven_high[id];
+            }
+        }
+
+        // ----- schedule updates where the bit differs -----------------------
+        for (p, high) in cell_high {
+            let cur_word = voxel_map.voxel_map[&p].state;
+            let bit_is_on = cur_word.get(ch);
+
+            if bit_is_on != high {
+                pending
+                    .entry(p)
+                    .and_modify(|w| {
+                        if high { w.set(ch) } else { w.clear(ch) }
+                        *w = clamp_state(&voxel_map.voxel_map[&p].kind, *w);
+                    })
+                    
+                    // inside or_insert_with
+                    .or_insert_with(|| {
+                        let mut w = cur_word;
+                        if high { w.set(ch) } else { w.clear(ch) }
+                        clamp_state(&voxel_map.voxel_map[&p].kind, w)
+                    });
+            }
+        }
     }
 
     // --- 3. convert the accumulated edits into LogicEvents ------------------
@@ -225,16 +620,19 @@ pub fn propagate_wires(voxel_map: &VoxelMap) -> Vec<LogicEvent> {
 
 
 
-fn simulate_gate(voxel: &Voxel, voxels: &VoxelMap, sim_timer: &mut SimulationTimer) -> Option<Bits16> {
-    // --- gather the two logical inputs (boolean) ---------------------------
+fn simulate_gate(
+    voxel: &Voxel,
+    voxels: &VoxelMap,
+    sim_timer: &mut SimulationTimer,
+    updates: &HashSet<IVec3>,
+    memory: &mut GateMemory,
+) -> Option<Bits16> {
+    // --- gather up to four logical inputs (boolean) ------------------------
     let (ins, _) = voxel_directions(voxel);
-    let mut in_sig = [false; 2];
+    let mut in_sig = [false; 4];
 
-    for (slot, &pos) in ins.iter().take(2).enumerate() {
-        in_sig[slot] = voxels
-            .voxel_map
-            .get(&pos)
-            .map_or(false, |v| v.state.any_set());
+    for (slot, &pos) in ins.iter().take(4).enumerate() {
+        in_sig[slot] = input_word(voxels, voxel.position, pos).any_set();
     }
 
     use VoxelType::*;
@@ -244,6 +642,15 @@ fn simulate_gate(voxel: &Voxel, voxels: &VoxelMap, sim_timer: &mut SimulationTim
     use NotVariants::*;
     use LatchVariants::*;
 
+    // ── word-valued gates ───────────────────────────────────────────────────
+    if let Component(ComponentVariants::Random(seed)) = voxel.kind {
+        if !memory.rising(voxel.position, in_sig[0]) {
+            return None;
+        }
+        let next = Bits16::new(next_random(voxel.state.value(), seed));
+        return (next != voxel.state).then_some(next);
+    }
+
     let out_bool = match voxel.kind {
         Not(NotGate)            => !in_sig[0],
         Not(BufferGate)         =>  in_sig[0],
@@ -257,13 +664,65 @@ fn simulate_gate(voxel: &Voxel, voxels: &VoxelMap, sim_timer: &mut SimulationTim
         Xor(XorGate)            =>  in_sig[0]  ^  in_sig[1],
         Xor(XnorGate)           => !(in_sig[0]  ^  in_sig[1]),
 
+        And(And3Gate)           =>  in_sig[..3].iter().all(|&s| s),
+        And(And4Gate)           =>  in_sig.iter().all(|&s| s),
+
+        Or(Or3Gate)             =>  in_sig[..3].iter().any(|&s| s),
+        Or(Or4Gate)             =>  in_sig.iter().any(|&s| s),
+
+        // odd parity
+        Xor(Xor3Gate)           =>  in_sig[..3].iter().filter(|&&s| s).count() % 2 == 1,
+        Xor(Xor4Gate)           =>  in_sig.iter().filter(|&&s| s).count() % 2 == 1,
+
         Latch(DFlipFlop) => {
             let d   = in_sig[1];
             let clk = in_sig[0];
             if clk { d } else { voxel.state.any_set() }
         }
+
+        Latch(SRLatch) => {
+            let (set, reset) = (in_sig[0], in_sig[1]);
+            match (set, reset) {
+                (true, false) => true,
+                (false, true) => false,
+                _             => voxel.state.any_set(), // hold (and ignore S = R = 1)
+            }
+        }
         
-        Component(ComponentVariants::Light) => in_sig[0],
+        Component(ComponentVariants::Light)
+        | Component(ComponentVariants::Piston) => in_sig[0],
+        
+        Component(ComponentVariants::Door) => ins
+            .iter()
+            .any(|&pos| input_word(voxels, voxel.position, pos).any_set()),
+        
+        Component(ComponentVariants::EdgeDetector(mode)) => {
+            let changed = memory.changed(voxel.position, in_sig[0]);
+            match mode {
+                EdgeMode::Rising  => changed && in_sig[0],
+                EdgeMode::Falling => changed && !in_sig[0],
+                EdgeMode::Both    => changed,
+            }
+        }
+
+        // high for `width` ticks after the input rises (a new edge restarts it)
+        Component(ComponentVariants::Monostable(width)) => {
+            if memory.rising(voxel.position, in_sig[0]) {
+                memory.pulse_ticks.insert(voxel.position, width);
+            }
+            match memory.pulse_ticks.get_mut(&voxel.position) {
+                Some(ticks) if *ticks > 0 => {
+                    *ticks -= 1;
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        // one-tick pulse after the watched cell was placed, removed or toggled
+        Component(ComponentVariants::UpdateDetector) => {
+            updates.contains(&(voxel.position + voxel_facing(voxel)))
+        }
         
         Component(ComponentVariants::Clock(speed)) => {
             let speed = speed as usize;
@@ -283,21 +742,335 @@ fn simulate_gate(voxel: &Voxel, voxels: &VoxelMap, sim_timer: &mut SimulationTim
     (new_state != voxel.state).then_some(new_state)
 }
 
-pub fn voxel_directions(voxel: &Voxel) -> (Vec<IVec3>, IVec3) {
-    // local → world rotation (XZ only)
-    let rot = |v: IVec3| match voxel.direction {
-        1 => v,                         // +Z forward
-        2 => IVec3::new( v.z, 0, -v.x), // +X forward:  (x,z) ↦ ( z,-x)
-        3 => IVec3::new(-v.x, 0, -v.z), // –Z forward
-        4 => IVec3::new(-v.z, 0,  v.x), // –X forward
-        d => { eprintln!("bad dir {d}"); v }
+/// Word a gate at `reader` sees on its input at `pos`.
+///
+/// A multi-output voxel shows the port that points at the reader. A cable whose
+/// face toward the gate is insulated reads as all zeros.
+pub fn input_word(voxels: &VoxelMap, reader: IVec3, pos: IVec3) -> Bits16 {
+    let Some(v) = voxels.voxel_map.get(&pos).filter(|v| !v.is_insulated(reader - pos)) else {
+        return Bits16::all_zeros();
     };
+    let (_, outputs) = voxel_directions(v);
+    let port = outputs.iter().position(|&out| out == reader).unwrap_or(0);
+    v.port_state(port)
+}
 
-    let IoPattern { inputs, output } = voxel.kind.io_pattern();
+/// Words for output ports 1.. given the word on port 0.
+fn extra_port_states(voxel: &Voxel, primary: Bits16) -> Vec<(usize, Bits16)> {
+    match voxel.kind {
+        VoxelType::Latch(LatchVariants::SRLatch) => vec![(1, bitword(!primary.any_set()))],
+        _ => Vec::new(),
+    }
+}
+
+/// Next word of a `Random` voxel: a 16-bit xorshift step over the current
+/// output mixed with the voxel's seed. Pure, so a saved world replays identically.
+pub fn next_random(current: u16, seed: u16) -> u16 {
+    let mut x = current ^ seed;
+    if x == 0 {
+        x = 0xACE1; // xorshift never leaves zero
+    }
+    x ^= x << 7;
+    x ^= x >> 9;
+    x ^= x << 8;
+    x
+}
+
+/// Seed given to a `Random` voxel when it is placed, derived from its position.
+pub fn position_seed(position: IVec3) -> u16 {
+    let hash = (position.x as u32).wrapping_mul(0x9E37_79B1)
+        ^ (position.y as u32).wrapping_mul(0x85EB_CA77)
+        ^ (position.z as u32).wrapping_mul(0xC2B2_AE3D);
+    (hash ^ (hash >> 16)) as u16
+}
+
+/// Local → world rotation of a voxel-relative offset (see `direction_yaw` / `direction_tilt`).
+fn rotate_local(direction: u8, v: IVec3) -> IVec3 {
+    // tilt about the local X axis first …
+    let v = match direction_tilt(direction) {
+        1  => IVec3::new(v.x,  v.z, -v.y), // +Y forward
+        -1 => IVec3::new(v.x, -v.z,  v.y), // –Y forward
+        _  => v,
+    };
+    // … then turn about Y
+    match direction_yaw(direction) {
+        1 => v,                           // +Z forward
+        2 => IVec3::new( v.z, v.y, -v.x), // +X forward:  (x,z) ↦ ( z,-x)
+        3 => IVec3::new(-v.x, v.y, -v.z), // –Z forward
+        4 => IVec3::new(-v.z, v.y,  v.x), // –X forward
+        d => { eprintln!("bad dir {d}"); v }
+    }
+}
+
+/// World-space unit vector pointing out of the front of `voxel`.
+pub fn voxel_facing(voxel: &Voxel) -> IVec3 {
+    rotate_local(voxel.direction, IVec3::Z)
+}
+
+/// World positions of a voxel's inputs, and of its outputs in port order.
+pub fn voxel_directions(voxel: &Voxel) -> (Vec<IVec3>, Vec<IVec3>) {
+    let rot = |v: IVec3| rotate_local(voxel.direction, v);
+
+    let (inputs, outputs): (Vec<IVec3>, Vec<IVec3>) = match voxel.kind {
+        VoxelType::Chip(chip) => (chip.input_faces(), chip.output_faces()),
+        kind => {
+            let IoPattern { inputs, outputs } = kind.io_pattern();
+            (inputs.to_vec(), outputs.iter().map(|port| port.offset).collect())
+        }
+    };
 
     // rotate + translate
-    let world_inputs : Vec<IVec3> = inputs.iter().map(|&v| voxel.position + rot(v)).collect();
-    let world_output              = output.map_or(IVec3::ZERO, |v| voxel.position + rot(v));
+    let world_inputs  : Vec<IVec3> = inputs.iter().map(|&v| voxel.position + rot(v)).collect();
+    let world_outputs : Vec<IVec3> = outputs.iter().map(|&v| voxel.position + rot(v)).collect();
     
-    (world_inputs, world_output)
+    (world_inputs, world_outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A loose circuit stepped the way `logic_system` and `logic_event_handler` do.
+    struct Circuit {
+        map: VoxelMap,
+        memory: GateMemory,
+        timer: SimulationTimer,
+    }
+
+    impl Circuit {
+        fn new(voxels: &[Voxel]) -> Self {
+            Self {
+                map: VoxelMap {
+                    entity_map: HashMap::new(),
+                    voxel_map: voxels.iter().map(|voxel| (voxel.position, *voxel)).collect(),
+                    asset_map: HashMap::new(),
+                    generation: 0,
+                },
+                memory: GateMemory::default(),
+                timer: SimulationTimer {
+                    tick: Timer::from_seconds(1.0, TimerMode::Repeating),
+                    rate: 1,
+                    tick_counter: 0,
+                },
+            }
+        }
+
+        fn run(&mut self, ticks: usize) {
+            for _ in 0..ticks {
+                let events = simulate_tick(
+                    &self.map,
+                    &mut self.timer,
+                    &HashSet::new(),
+                    &mut self.memory,
+                    &mut HashMap::new(),
+                    &ChipLibrary::default(),
+                    0,
+                );
+                for event in events {
+                    match event {
+                        LogicEvent::UpdateVoxel { position, new_state } => {
+                            self.map.voxel_map.get_mut(&position).unwrap().state = new_state;
+                        }
+                        LogicEvent::UpdatePort { position, port, new_state } => {
+                            self.map.voxel_map.get_mut(&position).unwrap().set_port_state(port, new_state);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        fn set(&mut self, position: IVec3, on: bool) {
+            self.map.voxel_map.get_mut(&position).unwrap().state = bitword(on);
+        }
+
+        fn high(&self, position: IVec3) -> bool {
+            self.map.voxel_map[&position].state.any_set()
+        }
+    }
+
+    fn switch(position: IVec3, direction: u8, on: bool) -> Voxel {
+        Voxel {
+            state: bitword(on),
+            ..fresh_voxel(VoxelType::Component(ComponentVariants::Switch), position, direction)
+        }
+    }
+
+    fn wire(position: IVec3) -> Voxel {
+        fresh_voxel(VoxelType::Wire(0), position, 1)
+    }
+
+    /// Output of a lone gate facing +Z with a switch on each of its inputs.
+    fn gate_output(kind: VoxelType, inputs: &[bool]) -> bool {
+        let gate = fresh_voxel(kind, IVec3::ZERO, 1);
+        let (cells, _) = voxel_directions(&gate);
+        let mut voxels = vec![gate];
+        voxels.extend(cells.iter().zip(inputs).map(|(&cell, &on)| switch(cell, 1, on)));
+        let mut circuit = Circuit::new(&voxels);
+        circuit.run(1);
+        circuit.high(IVec3::ZERO)
+    }
+
+    /// States of an edge detector fed `inputs`, one per tick.
+    fn edges(mode: EdgeMode, inputs: &[bool]) -> Vec<bool> {
+        let detector = fresh_voxel(VoxelType::Component(ComponentVariants::EdgeDetector(mode)), IVec3::ZERO, 1);
+        let mut circuit = Circuit::new(&[detector, switch(IVec3::NEG_Z, 1, false)]);
+        inputs
+            .iter()
+            .map(|&input| {
+                circuit.set(IVec3::NEG_Z, input);
+                circuit.run(1);
+                circuit.high(IVec3::ZERO)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn receivers_hear_transmitters_on_their_frequency() {
+        let receiver = |x: i32, frequency: usize, delayed: bool| {
+            let kind = VoxelType::Component(ComponentVariants::Receiver(frequency, delayed));
+            fresh_voxel(kind, IVec3::new(x, 0, 0), 1)
+        };
+        let mut circuit = Circuit::new(&[
+            switch(IVec3::NEG_Z, 1, true),
+            fresh_voxel(VoxelType::Component(ComponentVariants::Transmitter(3)), IVec3::ZERO, 1),
+            receiver(5, 3, false),
+            receiver(6, 3, true),
+            receiver(7, 4, false),
+        ]);
+
+        // A delayed receiver reads what the transmitter latched the tick before
+        circuit.run(1);
+        assert!(circuit.high(IVec3::new(5, 0, 0)));
+        assert!(!circuit.high(IVec3::new(6, 0, 0)));
+        circuit.run(1);
+        assert!(circuit.high(IVec3::new(6, 0, 0)));
+        assert!(!circuit.high(IVec3::new(7, 0, 0)));
+    }
+
+    #[test]
+    fn diode_passes_only_along_its_facing() {
+        // Switch → wire → diode → wire, with the diode facing away from the switch and then toward it
+        for (direction, passes) in [(1, true), (3, false)] {
+            let mut circuit = Circuit::new(&[
+                switch(IVec3::ZERO, 1, true),
+                wire(IVec3::new(0, 0, 1)),
+                fresh_voxel(VoxelType::Diode, IVec3::new(0, 0, 2), direction),
+                wire(IVec3::new(0, 0, 3)),
+            ]);
+            circuit.run(3);
+            assert!(circuit.high(IVec3::new(0, 0, 1)));
+            assert_eq!(circuit.high(IVec3::new(0, 0, 3)), passes, "diode direction {direction}");
+        }
+    }
+
+    #[test]
+    fn crossing_keeps_its_runs_apart() {
+        // Wires on both runs of a crossing at the origin, driven along one run at a time
+        let runs = [(IVec3::X, 2), (IVec3::Z, 1)];
+        for (along, direction) in runs {
+            let across = if along == IVec3::X { IVec3::Z } else { IVec3::X };
+            let mut circuit = Circuit::new(&[
+                fresh_voxel(VoxelType::WireCrossing, IVec3::ZERO, 1),
+                switch(-2 * along, direction, true),
+                wire(-along),
+                wire(along),
+                wire(-across),
+                wire(across),
+            ]);
+            circuit.run(3);
+            assert!(circuit.high(along), "run along {along}");
+            assert!(circuit.high(IVec3::ZERO));
+            assert!(!circuit.high(-across) && !circuit.high(across), "run along {along}");
+        }
+    }
+
+    #[test]
+    fn three_and_four_input_gates_follow_their_truth_tables() {
+        use VoxelType::*;
+        let gates = [
+            (And(AndVariants::And3Gate), 3),
+            (And(AndVariants::And4Gate), 4),
+            (Or(OrVariants::Or3Gate), 3),
+            (Or(OrVariants::Or4Gate), 4),
+            (Xor(XorVariants::Xor3Gate), 3),
+            (Xor(XorVariants::Xor4Gate), 4),
+        ];
+        for (kind, count) in gates {
+            for bits in 0..1u32 << count {
+                let inputs: Vec<bool> = (0..count).map(|i| bits >> i & 1 == 1).collect();
+                let ones = bits.count_ones() as usize;
+                let expected = match kind {
+                    And(_) => ones == count,
+                    Or(_) => ones > 0,
+                    _ => ones % 2 == 1,
+                };
+                assert_eq!(gate_output(kind, &inputs), expected, "{kind:?} {inputs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn sr_latch_shows_q_in_front_and_not_q_behind() {
+        let latch = fresh_voxel(VoxelType::Latch(LatchVariants::SRLatch), IVec3::ZERO, 1);
+        let mut circuit = Circuit::new(&[latch, switch(IVec3::NEG_X, 1, false), switch(IVec3::X, 1, false)]);
+        // Set, hold, reset, hold, then both at once, which holds
+        let steps = [
+            (false, false, false),
+            (true, false, true),
+            (false, false, true),
+            (false, true, false),
+            (false, false, false),
+            (true, true, false),
+        ];
+        for (set, reset, q) in steps {
+            circuit.set(IVec3::NEG_X, set);
+            circuit.set(IVec3::X, reset);
+            circuit.run(1);
+            let front = input_word(&circuit.map, IVec3::Z, IVec3::ZERO).any_set();
+            let back = input_word(&circuit.map, IVec3::NEG_Z, IVec3::ZERO).any_set();
+            assert_eq!((front, back), (q, !q), "set {set} reset {reset}");
+        }
+    }
+
+    #[test]
+    fn edge_detectors_pulse_for_one_tick() {
+        let inputs = [false, true, true, false, false, true];
+        assert_eq!(edges(EdgeMode::Rising, &inputs), [false, true, false, false, false, true]);
+        assert_eq!(edges(EdgeMode::Falling, &inputs), [false, false, false, true, false, false]);
+        assert_eq!(edges(EdgeMode::Both, &inputs), [false, true, false, true, false, true]);
+    }
+
+    #[test]
+    fn random_sequence_repeats_for_a_seed() {
+        let sequence = |seed: u16| -> Vec<u16> {
+            std::iter::successors(Some(0), |&word| Some(next_random(word, seed)))
+                .skip(1)
+                .take(32)
+                .collect()
+        };
+        let seed = position_seed(IVec3::new(3, -1, 7));
+        assert_eq!(seed, position_seed(IVec3::new(3, -1, 7)));
+        assert_ne!(seed, position_seed(IVec3::new(3, -1, 8)));
+        assert_eq!(sequence(seed), sequence(seed));
+        assert_ne!(sequence(seed), sequence(seed ^ 1));
+        assert!(sequence(seed).iter().all(|&word| word != 0));
+    }
+
+    #[test]
+    fn random_voxel_steps_on_rising_clock_edges() {
+        let random = fresh_voxel(VoxelType::Component(ComponentVariants::Random(0)), IVec3::ZERO, 1);
+        let VoxelType::Component(ComponentVariants::Random(seed)) = random.kind else {
+            unreachable!();
+        };
+        let first = next_random(0, seed);
+        let second = next_random(first, seed);
+
+        let mut circuit = Circuit::new(&[random, switch(IVec3::NEG_Z, 1, false)]);
+        for (clock, expected) in [(false, 0), (true, first), (true, first), (false, first), (true, second)] {
+            circuit.set(IVec3::NEG_Z, clock);
+            circuit.run(1);
+            assert_eq!(circuit.map.voxel_map[&IVec3::ZERO].state.value(), expected);
+        }
+    }
 }
@@ -1,2 +1,6 @@
 pub mod logic_handler;
-pub mod graphics;
\ No newline at end of file
+pub mod graphics;
+pub mod sensors;
+pub mod actuators;
+pub mod detectors;
+pub mod chips;
\ No newline at end of file
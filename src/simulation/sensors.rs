@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use bevy_fps_controller::controller::LogicalPlayer;
+use bevy_rapier3d::prelude::CollisionEvent;
+
+use crate::prelude::*;
+
+/// Drives the voxels that react to the player instead of a key press.
+///
+/// * A `PressurePlate` is high while the `LogicalPlayer` collider rests on top of it.
+///   Contacts come from rapier collision events between the player and the voxel colliders.
+/// * A `ProximitySensor(radius)` is high while the camera is within `radius` blocks.
+pub fn sensor_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut contacts: Local<HashSet<Entity>>,
+    player_query: Query<(Entity, &Transform), With<LogicalPlayer>>,
+    voxel_query: Query<&Voxel>,
+    sensor_query: Query<&Voxel, With<SensorVoxel>>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut logic_writer: EventWriter<LogicEvent>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+
+    // ── 1. keep track of everything the player collider is touching ─────────
+    for event in collision_events.read() {
+        match *event {
+            CollisionEvent::Started(a, b, _) => {
+                if let Some(other) = other_collider(player_entity, a, b) {
+                    contacts.insert(other);
+                }
+            }
+            CollisionEvent::Stopped(a, b, _) => {
+                if let Some(other) = other_collider(player_entity, a, b) {
+                    contacts.remove(&other);
+                }
+            }
+        }
+    }
+
+    // ── 2. plates count only when touched from above ────────────────────────
+    let pressed_plates: HashSet<IVec3> = contacts
+        .iter()
+        .filter_map(|&entity| voxel_query.get(entity).ok())
+        .filter(|voxel| matches!(voxel.kind, VoxelType::Component(ComponentVariants::PressurePlate)))
+        .filter(|voxel| voxel.position.y as f32 + VOXEL_COLLIDER_SIZE <= player_transform.translation.y)
+        .map(|voxel| voxel.position)
+        .collect();
+
+    // ── 3. schedule state changes for every sensor ──────────────────────────
+    for sensor in sensor_query.iter() {
+        // The map holds the latest state, the component may lag a frame behind
+        let position = sensor.position;
+        let Some(voxel) = voxel_map.voxel_map.get(&position) else {
+            continue;
+        };
+        let active = match voxel.kind {
+            VoxelType::Component(ComponentVariants::PressurePlate) => {
+                pressed_plates.contains(&position)
+            }
+            VoxelType::Component(ComponentVariants::ProximitySensor(radius)) => {
+                player.camera_pos.distance(position.as_vec3()) <= radius as f32
+            }
+            _ => continue,
+        };
+
+        let new_state = bitword(active);
+        if voxel.state != new_state {
+            logic_writer.send(LogicEvent::UpdateVoxel { position, new_state });
+        }
+    }
+}
+
+/// Returns the collider on the other side of a contact involving the player.
+fn other_collider(player: Entity, a: Entity, b: Entity) -> Option<Entity> {
+    if a == player {
+        Some(b)
+    } else if b == player {
+        Some(a)
+    } else {
+        None
+    }
+}
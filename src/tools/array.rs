@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const ARRAY_COLOR: Color = Color::srgb(0.9, 0.5, 1.0);
+const ARRAY_INCREMENT_COLOR: Color = Color::srgb(0.4, 1.0, 0.5);
+
+/// How the array tool repeats the selection.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ArraySettings {
+    /// Slices after stamping, the selection itself included.
+    pub count: i32,
+    /// Cells between the lowest corners of neighbouring slices.
+    pub stride: i32,
+    /// Move `Wire(n)` to `Wire(n + 1)` with every slice, wrapping after the last channel.
+    pub increment_channels: bool,
+}
+
+impl Default for ArraySettings {
+    fn default() -> Self {
+        Self {
+            count: 2,
+            stride: 1,
+            increment_channels: false,
+        }
+    }
+}
+
+/// Axis the slices are stamped along: the dominant component of the camera direction.
+fn array_axis(camera_dir: Vec3) -> IVec3 {
+    let abs = camera_dir.abs();
+    if abs.y >= abs.x && abs.y >= abs.z {
+        IVec3::Y * camera_dir.y.signum() as i32
+    } else if abs.x >= abs.z {
+        IVec3::X * camera_dir.x.signum() as i32
+    } else {
+        IVec3::Z * camera_dir.z.signum() as i32
+    }
+}
+
+/// `kind` as it appears in the `slice`-th copy.
+fn slice_kind(kind: VoxelType, slice: i32, increment_channels: bool) -> VoxelType {
+    match kind {
+        VoxelType::Wire(channel) if increment_channels => {
+            // One channel per bit of a bundle
+            VoxelType::Wire((channel as i32 + slice).rem_euclid(u16::BITS as i32) as u8)
+        }
+        kind => kind,
+    }
+}
+
+/// Array tool for stamping repeated slices of the selection.
+///
+/// N toggles the tool while a box is selected; the slices run the way the camera
+/// looks. = / - change the slice count, ] / [ the stride and C toggles the wire
+/// channel increment. Left click stamps, right click leaves the tool.
+pub fn array_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    selection: Res<Selection>,
+    mut settings: ResMut<ArraySettings>,
+    mut tool: ResMut<ActiveTool>,
+    mut gizmos: Gizmos,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    if *current_ui != GameUI::Default || keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+    let Some((min, max)) = selection.bounds() else {
+        if *tool == ActiveTool::Array {
+            *tool = ActiveTool::None;
+        }
+        return;
+    };
+    let axis = array_axis(player.camera_dir);
+    let extent = (max - min + IVec3::ONE).dot(axis.abs());
+
+    if keyboard.just_pressed(KeyCode::KeyN) {
+        if *tool == ActiveTool::Array {
+            *tool = ActiveTool::None;
+        } else if *tool == ActiveTool::None {
+            *tool = ActiveTool::Array;
+            // Start with the slices right next to each other
+            settings.stride = extent;
+        }
+        return;
+    }
+    if *tool != ActiveTool::Array {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Equal) {
+        settings.count = (settings.count + 1).min(ARRAY_MAX_COUNT);
+    }
+    if keyboard.just_pressed(KeyCode::Minus) {
+        settings.count = (settings.count - 1).max(2);
+    }
+    if keyboard.just_pressed(KeyCode::BracketRight) {
+        settings.stride += 1;
+    }
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        settings.stride = (settings.stride - 1).max(1);
+    }
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        settings.increment_channels = !settings.increment_channels;
+    }
+
+    let color = if settings.increment_channels { ARRAY_INCREMENT_COLOR } else { ARRAY_COLOR };
+    let size = (max - min + IVec3::ONE).as_vec3();
+    for slice in 1..settings.count {
+        let offset = axis * settings.stride * slice;
+        let center = (min + max + offset * 2).as_vec3() / 2.0;
+        gizmos.cuboid(Transform::from_translation(center).with_scale(size), color);
+    }
+
+    if mouse.just_pressed(MouseButton::Right) {
+        *tool = ActiveTool::None;
+        return;
+    }
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    // The tool readout says why
+    if selection.check_volume().is_err() {
+        return;
+    }
+
+    let ArraySettings { count, stride, increment_channels } = *settings;
+    let source = Blueprint::from_region(&voxel_map, min, max, String::new());
+    let edits: Vec<(IVec3, Option<Voxel>)> = (1..count)
+        .flat_map(|slice| {
+            let offset = min + axis * stride * slice;
+            source.voxels.iter().map(move |voxel| {
+                let position = offset + voxel.position;
+                let kind = slice_kind(voxel.kind, slice, increment_channels);
+                (position, Some(Voxel { kind, position, ..*voxel }))
+            })
+        })
+        .collect();
+    if edits.is_empty() {
+        return;
+    }
+
+    audio_writer.send(AudioEvent::World(WorldSfx::Place, min + axis * stride));
+    apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+}
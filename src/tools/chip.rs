@@ -0,0 +1,306 @@
+use bevy::{prelude::*, window::CursorGrabMode};
+use bevy_simple_text_input::TextInputSubmitEvent;
+
+use crate::prelude::*;
+
+const INPUT_PIN_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+const OUTPUT_PIN_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+
+/// Faces tried in order when giving pins a side of the chip voxel: outputs at the
+/// front like a gate, inputs on the sides and back.
+const OUTPUT_FACE_ORDER: [IVec3; 6] = [IVec3::Z, IVec3::Y, IVec3::NEG_Y, IVec3::X, IVec3::NEG_X, IVec3::NEG_Z];
+const INPUT_FACE_ORDER: [IVec3; 6] = [IVec3::NEG_X, IVec3::X, IVec3::NEG_Z, IVec3::Y, IVec3::NEG_Y, IVec3::Z];
+
+/// Cable cells of the selection marked as pins of the chip being packaged, in
+/// the order they were marked.
+#[derive(Resource, Default)]
+pub struct ChipDraft {
+    pub inputs: Vec<IVec3>,
+    pub outputs: Vec<IVec3>,
+}
+
+impl ChipDraft {
+    fn clear(&mut self) {
+        self.inputs.clear();
+        self.outputs.clear();
+    }
+
+    /// Marks `cell` in `pins`, or unmarks it if it already was. A cell is only
+    /// ever one kind of pin.
+    fn toggle(pins: &mut Vec<IVec3>, others: &mut Vec<IVec3>, cell: IVec3) {
+        others.retain(|pin| *pin != cell);
+        match pins.iter().position(|pin| *pin == cell) {
+            Some(index) => {
+                pins.remove(index);
+            }
+            None => pins.push(cell),
+        }
+    }
+}
+
+/// Packages the selection as a custom chip.
+///
+/// With a region selected, K starts the tool. Left click a wire or bundled wire
+/// inside the selection to mark it as an input pin, right click to mark an
+/// output pin; clicking a pin again unmarks it. K again names the chip and its
+/// pins, Shift+K leaves the tool.
+pub fn chip_tool_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    selection: Res<Selection>,
+    mut draft: ResMut<ChipDraft>,
+    mut tool: ResMut<ActiveTool>,
+    mut message: ResMut<ToolMessage>,
+    mut gizmos: Gizmos,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    if *current_ui != GameUI::Default || keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+
+    let Some((min, max)) = selection.bounds() else {
+        if *tool == ActiveTool::Chip {
+            *tool = ActiveTool::None;
+            draft.clear();
+        }
+        return;
+    };
+
+    if keyboard.just_pressed(KeyCode::KeyK) {
+        match *tool {
+            ActiveTool::Chip if keyboard.pressed(KeyCode::ShiftLeft) => {
+                *tool = ActiveTool::None;
+                draft.clear();
+            }
+            ActiveTool::Chip if draft.outputs.is_empty() => {
+                message.show("Mark at least one output pin with right click");
+            }
+            ActiveTool::Chip => {
+                event_writer.send(GameEvent::UpdateCursorMode {
+                    mode: CursorGrabMode::None,
+                    show_cursor: true,
+                    enable_input: false,
+                });
+                event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::ChipWidget });
+            }
+            ActiveTool::None => {
+                *tool = ActiveTool::Chip;
+                draft.clear();
+            }
+            _ => {}
+        }
+        return;
+    }
+    if *tool != ActiveTool::Chip {
+        return;
+    }
+
+    for (pins, color) in [(&draft.inputs, INPUT_PIN_COLOR), (&draft.outputs, OUTPUT_PIN_COLOR)] {
+        for pin in pins {
+            gizmos.cuboid(
+                Transform::from_translation(pin.as_vec3()).with_scale(Vec3::splat(1.05)),
+                color,
+            );
+        }
+    }
+
+    let left = mouse.just_pressed(MouseButton::Left);
+    if !left && !mouse.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Some(voxel) = player.hit_voxel else {
+        return;
+    };
+    let inside = voxel.position.cmpge(min).all() && voxel.position.cmple(max).all();
+    if !inside || !matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire) {
+        message.show("Pins must be wires inside the selection");
+        return;
+    }
+
+    let draft = &mut *draft;
+    let already_marked = draft.inputs.contains(&voxel.position) || draft.outputs.contains(&voxel.position);
+    if !already_marked && draft.inputs.len() + draft.outputs.len() >= CHIP_FACES.len() {
+        message.show(format!("A chip has at most {} pins", CHIP_FACES.len()));
+        return;
+    }
+    if left {
+        ChipDraft::toggle(&mut draft.inputs, &mut draft.outputs, voxel.position);
+    } else if draft.outputs.len() < MAX_OUTPUT_PORTS || draft.outputs.contains(&voxel.position) {
+        ChipDraft::toggle(&mut draft.outputs, &mut draft.inputs, voxel.position);
+    } else {
+        message.show(format!("A chip has at most {} outputs", MAX_OUTPUT_PORTS));
+    }
+}
+
+/// Splits "name a b > x y" into the chip name and the input / output pin names.
+/// Pins left unnamed are called In1, In2, ... and Out1, Out2, ...
+fn parse_chip_names(
+    text: &str,
+    inputs: usize,
+    outputs: usize,
+) -> Result<(String, Vec<String>, Vec<String>), String> {
+    let text = text.replace("->", ">");
+    let (left, right) = text.split_once('>').unwrap_or((&text, ""));
+    let mut left = left.split_whitespace();
+
+    let name: String = left
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if name.is_empty() {
+        return Err("the chip needs a name".to_string());
+    }
+
+    let pin_names = |given: Vec<&str>, count: usize, prefix: &str| {
+        if given.len() > count {
+            return Err(format!("{} {prefix} names for {count} pins", given.len()));
+        }
+        Ok((0..count)
+            .map(|i| given.get(i).map_or(format!("{prefix}{}", i + 1), |name| name.to_string()))
+            .collect())
+    };
+    let input_names = pin_names(left.collect(), inputs, "In")?;
+    let output_names = pin_names(right.split_whitespace().collect(), outputs, "Out")?;
+    Ok((name, input_names, output_names))
+}
+
+/// Gives each pin the first face of `order` not taken yet.
+fn assign_faces(cells: &[IVec3], names: Vec<String>, order: &[IVec3], taken: &mut u8, min: IVec3) -> Vec<ChipPin> {
+    let mut pins: Vec<ChipPin> = cells
+        .iter()
+        .zip(names)
+        .map(|(cell, name)| {
+            let face = order
+                .iter()
+                .map(|offset| CHIP_FACES.iter().position(|face| face == offset).unwrap() as u8)
+                .find(|face| *taken & (1 << face) == 0)
+                .unwrap_or_default();
+            *taken |= 1 << face;
+            ChipPin {
+                name,
+                position: *cell - min,
+                face,
+            }
+        })
+        .collect();
+    pins.sort_by_key(|pin| pin.face);
+    pins
+}
+
+/// Builds the chip definition for the selection and the marked pins.
+fn package_chip(
+    text: &str,
+    draft: &ChipDraft,
+    selection: &Selection,
+    voxel_map: &VoxelMap,
+    library: &ChipLibrary,
+) -> Result<ChipDefinition, String> {
+    let (min, max) = selection.bounds().ok_or("nothing is selected")?;
+    let (name, input_names, output_names) = parse_chip_names(text, draft.inputs.len(), draft.outputs.len())?;
+
+    let is_cable = |cell: &IVec3| {
+        matches!(
+            voxel_map.voxel_map.get(cell).map(|voxel| voxel.kind),
+            Some(VoxelType::Wire(_) | VoxelType::BundledWire)
+        )
+    };
+    if !draft.inputs.iter().chain(&draft.outputs).all(is_cable) {
+        return Err("a pin is no longer a wire".to_string());
+    }
+
+    let mut taken = 0u8;
+    let outputs = assign_faces(&draft.outputs, output_names, &OUTPUT_FACE_ORDER, &mut taken, min);
+    let inputs = assign_faces(&draft.inputs, input_names, &INPUT_FACE_ORDER, &mut taken, min);
+    let definition = ChipDefinition {
+        voxels: Blueprint::from_region(voxel_map, min, max, name.clone()).voxels,
+        name,
+        inputs,
+        outputs,
+    };
+
+    if definition.contains_chip(definition.id(), library) {
+        return Err(format!("{} can't contain itself", definition.name));
+    }
+    // Placed copies keep the ports they were placed with, so the pins may not move
+    if let Some(existing) = library.chips.get(&definition.id()) {
+        // Another name with the same hash would replace that chip everywhere
+        if existing.name != definition.name {
+            return Err(format!("{} clashes with the chip {}, pick a new name", definition.name, existing.name));
+        }
+        if existing.kind() != definition.kind() {
+            return Err(format!("{} exists with other pin faces, pick a new name", definition.name));
+        }
+    }
+    ChipState::new(&definition)?;
+    Ok(definition)
+}
+
+/// Saves the chip named in the chip widget and puts it in the hotbar.
+pub fn chip_submit_system(
+    mut submit_events: EventReader<TextInputSubmitEvent>,
+    chip_inputs: Query<(), With<ChipNameInput>>,
+    mut status_query: Query<&mut Text, With<ChipStatusText>>,
+    player: Res<Player>,
+    selection: Res<Selection>,
+    voxel_map: Res<VoxelMap>,
+    mut library: ResMut<ChipLibrary>,
+    mut instances: ResMut<ChipInstances>,
+    mut draft: ResMut<ChipDraft>,
+    mut tool: ResMut<ActiveTool>,
+    mut message: ResMut<ToolMessage>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    for event in submit_events.read() {
+        if !chip_inputs.contains(event.entity) {
+            continue;
+        }
+        let result = package_chip(&event.value, &draft, &selection, &voxel_map, &library)
+            .and_then(|definition| save_chip(&definition).map(|_| definition).map_err(|e| e.to_string()));
+        let definition = match result {
+            Ok(definition) => definition,
+            Err(error) => {
+                for mut text in status_query.iter_mut() {
+                    text.0 = format!("Error: {error}");
+                }
+                continue;
+            }
+        };
+
+        let pin_names = |pins: &[ChipPin]| pins.iter().map(|pin| pin.name.as_str()).collect::<Vec<_>>().join(", ");
+        message.show(format!(
+            "Saved chip {} with inputs {} and outputs {}",
+            definition.name,
+            pin_names(&definition.inputs),
+            pin_names(&definition.outputs)
+        ));
+        for mut text in status_query.iter_mut() {
+            text.0.clear();
+        }
+
+        // Placed copies restart with the new circuit
+        let id = definition.id();
+        instances.chips.retain(|_, state| state.id != id);
+
+        let kind = VoxelType::Chip(definition.kind());
+        library.chips.insert(id, definition);
+        let mut updated_player = player.clone();
+        updated_player.hotbar_selector = kind.group();
+        updated_player.hotbar[kind.group()] = kind;
+        event_writer.send(GameEvent::ModifyPlayer {
+            player_modified: updated_player,
+        });
+
+        draft.clear();
+        *tool = ActiveTool::None;
+        event_writer.send(GameEvent::UpdateCursorMode {
+            mode: CursorGrabMode::Locked,
+            show_cursor: false,
+            enable_input: true,
+        });
+        event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::Default });
+    }
+}
@@ -0,0 +1,428 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const GHOST_COLOR: Color = Color::srgba(0.55, 0.85, 1.0, 0.45);
+
+/// A copied region. Positions are relative to its lowest corner.
+#[derive(Clone, Debug, Default)]
+pub struct Blueprint {
+    pub name: String,
+    pub voxels: Vec<Voxel>,
+    /// Labels on the copied voxels, positioned like them.
+    pub labels: Vec<VoxelLabel>,
+}
+
+impl Blueprint {
+    /// Copies every voxel between `min` and `max` (inclusive), keeping kinds,
+    /// directions and states.
+    pub fn from_region(voxel_map: &VoxelMap, min: IVec3, max: IVec3, name: String) -> Self {
+        let voxels = voxel_map
+            .voxel_map
+            .values()
+            .filter(|voxel| voxel.position.cmpge(min).all() && voxel.position.cmple(max).all())
+            .map(|voxel| Voxel {
+                position: voxel.position - min,
+                ..*voxel
+            })
+            .collect();
+        Blueprint { name, voxels, labels: Vec::new() }
+    }
+
+    /// Takes along the labels of the region `from_region` copied from `min` to `max`.
+    pub fn with_labels(mut self, labels: &[VoxelLabel], min: IVec3, max: IVec3) -> Self {
+        self.labels = labels
+            .iter()
+            .filter(|label| label.position.cmpge(min).all() && label.position.cmple(max).all())
+            .map(|label| VoxelLabel {
+                position: label.position - min,
+                ..label.clone()
+            })
+            .collect();
+        self
+    }
+
+    /// The blueprint mirrored along X (if requested), then turned `turns` quarter turns
+    /// about Y, shifted back so the lowest corner sits at the origin again.
+    /// Insulated faces and labels turn with the voxel.
+    pub fn transformed(&self, turns: u8, mirrored: bool) -> Blueprint {
+        let transform = |mut offset: IVec3| {
+            if mirrored {
+                offset.x = -offset.x;
+            }
+            rotated_offset(offset, turns)
+        };
+        let mut voxels: Vec<Voxel> = self
+            .voxels
+            .iter()
+            .map(|voxel| {
+                let mut direction = voxel.direction;
+                if mirrored {
+                    direction = mirrored_direction(direction, IVec3::X);
+                }
+                for _ in 0..turns % 4 {
+                    direction = rotated_direction(direction, false);
+                }
+                Voxel {
+                    position: transform(voxel.position),
+                    direction,
+                    insulated: transformed_insulation(voxel.insulated, transform),
+                    ..*voxel
+                }
+            })
+            .collect();
+
+        let min = voxels
+            .iter()
+            .map(|voxel| voxel.position)
+            .reduce(IVec3::min)
+            .unwrap_or_default();
+        for voxel in &mut voxels {
+            voxel.position -= min;
+        }
+        let labels = self
+            .labels
+            .iter()
+            .map(|label| VoxelLabel {
+                position: transform(label.position) - min,
+                face: transform(label.face),
+                ..label.clone()
+            })
+            .collect();
+        Blueprint {
+            name: self.name.clone(),
+            voxels,
+            labels,
+        }
+    }
+}
+
+/// Copied blueprint and how it is turned for pasting. Kept for the whole session
+/// so a copy can be pasted into another world.
+#[derive(Resource, Default)]
+pub struct Clipboard {
+    pub blueprint: Option<Blueprint>,
+    pub turns: u8,
+    pub mirrored: bool,
+}
+
+/// Parent of the translucent preview shown while pasting.
+#[derive(Component)]
+pub struct PasteGhost;
+
+/// Preview voxel, ignored by the crosshair raycast.
+#[derive(Component)]
+pub struct GhostVoxel;
+
+/// Copy / paste and blueprint files.
+///
+/// Ctrl+C copies the selection, Ctrl+V toggles paste mode, Ctrl+S saves the
+/// clipboard to `BLUEPRINT_PATH` and Ctrl+O loads the next blueprint from there.
+/// In paste mode R / Shift+R turn the preview, M mirrors it, left click pastes
+/// with the lowest corner in the cell in front of the crosshair and right click
+/// leaves paste mode.
+pub fn clipboard_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    selection: Res<Selection>,
+    save_game: Res<SavedWorld>,
+    mut clipboard: ResMut<Clipboard>,
+    mut tool: ResMut<ActiveTool>,
+    mut message: ResMut<ToolMessage>,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    if *current_ui != GameUI::Default {
+        return;
+    }
+
+    if keyboard.pressed(KeyCode::ControlLeft) {
+        if keyboard.just_pressed(KeyCode::KeyC) {
+            copy_selection(&selection, &voxel_map, &save_game, &mut clipboard, &mut message);
+        } else if keyboard.just_pressed(KeyCode::KeyV) && clipboard.blueprint.is_some() {
+            *tool = if *tool == ActiveTool::Paste { ActiveTool::None } else { ActiveTool::Paste };
+        } else if keyboard.just_pressed(KeyCode::KeyS) {
+            if let Some(blueprint) = &clipboard.blueprint {
+                match save_blueprint(blueprint) {
+                    Ok(()) => message.show(format!("Saved blueprint {}", blueprint.name)),
+                    Err(error) => message.show(format!("Couldn't save blueprint {}: {error}", blueprint.name)),
+                }
+            }
+        } else if keyboard.just_pressed(KeyCode::KeyO) {
+            load_next_blueprint(&mut clipboard, &mut message);
+            if clipboard.blueprint.is_some() {
+                *tool = ActiveTool::Paste;
+            }
+        }
+        return;
+    }
+
+    if *tool != ActiveTool::Paste {
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        let step = if keyboard.pressed(KeyCode::ShiftLeft) { 3 } else { 1 };
+        clipboard.turns = (clipboard.turns + step) % 4;
+    }
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        clipboard.mirrored = !clipboard.mirrored;
+    }
+    if mouse.just_pressed(MouseButton::Right) {
+        *tool = ActiveTool::None;
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Some(blueprint), Some(anchor)) = (&clipboard.blueprint, player.selected_voxel) else {
+        return;
+    };
+    let pasted = blueprint.transformed(clipboard.turns, clipboard.mirrored);
+    let edits: Vec<(IVec3, Option<Voxel>)> = pasted
+        .voxels
+        .into_iter()
+        .map(|voxel| {
+            let position = anchor.position + voxel.position;
+            (position, Some(Voxel { position, ..voxel }))
+        })
+        .collect();
+    if edits.is_empty() {
+        return;
+    }
+
+    audio_writer.send(AudioEvent::World(WorldSfx::Place, anchor.position));
+    apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+    if !pasted.labels.is_empty() {
+        let labels = pasted
+            .labels
+            .into_iter()
+            .map(|label| VoxelLabel {
+                position: anchor.position + label.position,
+                ..label
+            })
+            .collect();
+        event_writer.send(GameEvent::AddLabels { labels });
+    }
+}
+
+fn copy_selection(
+    selection: &Selection,
+    voxel_map: &VoxelMap,
+    save_game: &SavedWorld,
+    clipboard: &mut Clipboard,
+    message: &mut ToolMessage,
+) {
+    let Some((min, max)) = selection.bounds() else {
+        return;
+    };
+    // The tool readout says why
+    if selection.check_volume().is_err() {
+        return;
+    }
+
+    // Name it after the world, with the first number not taken by a saved blueprint
+    let taken = blueprint_names();
+    let name = (1..)
+        .map(|n| format!("{}_{n}", save_game.world_name))
+        .find(|name| !taken.contains(name))
+        .expect("ran out of blueprint names");
+
+    let blueprint = Blueprint::from_region(voxel_map, min, max, name).with_labels(&save_game.labels, min, max);
+    message.show(format!("Copied {} voxels as {}", blueprint.voxels.len(), blueprint.name));
+    clipboard.blueprint = Some(blueprint);
+    clipboard.turns = 0;
+    clipboard.mirrored = false;
+}
+
+/// Replaces the clipboard with the blueprint after the current one, alphabetically.
+fn load_next_blueprint(clipboard: &mut Clipboard, message: &mut ToolMessage) {
+    let names = blueprint_names();
+    let current = clipboard.blueprint.as_ref().map(|blueprint| blueprint.name.as_str());
+    let next = names
+        .iter()
+        .find(|name| Some(name.as_str()) > current)
+        .or(names.first());
+    let Some(name) = next else {
+        return;
+    };
+
+    match load_blueprint(name) {
+        Ok(blueprint) => {
+            message.show(format!("Loaded blueprint {name}"));
+            clipboard.blueprint = Some(blueprint);
+            clipboard.turns = 0;
+            clipboard.mirrored = false;
+        }
+        Err(error) => message.show(format!("Couldn't load blueprint {name}: {error}")),
+    }
+}
+
+/// Keeps the translucent paste preview in front of the crosshair. The ghost is
+/// rebuilt whenever the clipboard or tool changes and only moved otherwise.
+pub fn update_paste_preview(
+    mut commands: Commands,
+    tool: Res<ActiveTool>,
+    clipboard: Res<Clipboard>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut ghosts: Query<(Entity, &mut Transform, &mut Visibility), With<PasteGhost>>,
+) {
+    if tool.is_changed() || clipboard.is_changed() {
+        for (entity, ..) in ghosts.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        if *tool == ActiveTool::Paste {
+            if let Some(blueprint) = &clipboard.blueprint {
+                let turned = blueprint.transformed(clipboard.turns, clipboard.mirrored);
+                spawn_ghost(&mut commands, &turned.voxels, &voxel_map, &mut materials);
+            }
+        }
+        return;
+    }
+
+    for (_, mut transform, mut visibility) in ghosts.iter_mut() {
+        match player.selected_voxel {
+            Some(anchor) => {
+                transform.translation = anchor.position.as_vec3();
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn spawn_ghost(
+    commands: &mut Commands,
+    voxels: &[Voxel],
+    voxel_map: &VoxelMap,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    commands
+        .spawn((
+            Transform::default(),
+            Visibility::Hidden,
+            PasteGhost,
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            for voxel in voxels {
+                let Some(asset) = voxel_map.asset(voxel.kind) else {
+                    continue;
+                };
+                let Some(template) = materials.get(&asset.material_handle) else {
+                    continue;
+                };
+                let material = StandardMaterial {
+                    base_color: GHOST_COLOR,
+                    alpha_mode: AlphaMode::Blend,
+                    ..template.clone()
+                };
+                parent.spawn((
+                    Mesh3d(asset.mesh_handle.clone()),
+                    MeshMaterial3d(materials.add(material)),
+                    compute_voxel_transform(voxel),
+                    GhostVoxel,
+                ));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voxel(position: IVec3, direction: u8, insulated: u8) -> Voxel {
+        Voxel {
+            insulated,
+            ..fresh_voxel(VoxelType::BundledWire, position, direction)
+        }
+    }
+
+    /// Position, direction and insulation of every voxel, in a stable order.
+    fn layout(voxels: &[Voxel]) -> Vec<(IVec3, u8, u8)> {
+        let mut layout: Vec<(IVec3, u8, u8)> = voxels
+            .iter()
+            .map(|voxel| (voxel.position, voxel.direction, voxel.insulated))
+            .collect();
+        layout.sort_by_key(|(position, ..)| position.to_array());
+        layout
+    }
+
+    fn blueprint() -> Blueprint {
+        Blueprint {
+            name: "test".to_string(),
+            voxels: vec![
+                voxel(IVec3::ZERO, 1, 0),
+                // Facing +X with its +X face insulated
+                voxel(IVec3::new(2, 0, 1), 2, 0b000001),
+                voxel(IVec3::new(1, 3, 0), 6, 0b010100),
+            ],
+            labels: vec![VoxelLabel {
+                position: IVec3::new(2, 0, 1),
+                face: IVec3::X,
+                text: "clk".to_string(),
+                always_visible: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn quarter_turn_moves_positions_directions_and_insulation() {
+        let turned = blueprint().transformed(1, false).voxels;
+        let moved = turned
+            .iter()
+            .find(|voxel| voxel.direction == 3)
+            .expect("the +X voxel faces -Z after a quarter turn");
+        assert_eq!(moved.position, IVec3::new(1, 0, 0));
+        assert_eq!(moved.insulated, 1 << face_index(IVec3::NEG_Z).unwrap());
+        assert!(turned.iter().all(|voxel| voxel.position.cmpge(IVec3::ZERO).all()));
+    }
+
+    #[test]
+    fn four_quarter_turns_round_trip() {
+        let original = blueprint();
+        let mut turned = original.clone();
+        for _ in 0..4 {
+            turned = turned.transformed(1, false);
+        }
+        assert_eq!(layout(&turned.voxels), layout(&original.voxels));
+        assert_eq!(turned.labels, original.labels);
+    }
+
+    #[test]
+    fn mirror_flips_x_facing_and_faces() {
+        let mirrored = blueprint().transformed(0, true).voxels;
+        let flipped = mirrored
+            .iter()
+            .find(|voxel| voxel.direction == 4)
+            .expect("the +X voxel faces -X once mirrored");
+        assert_eq!(flipped.position, IVec3::new(0, 0, 1));
+        assert_eq!(flipped.insulated, 1 << face_index(IVec3::NEG_X).unwrap());
+    }
+
+    #[test]
+    fn mirroring_twice_round_trips() {
+        let original = blueprint();
+        let twice = original.transformed(0, true).transformed(0, true);
+        assert_eq!(layout(&twice.voxels), layout(&original.voxels));
+        assert_eq!(twice.labels, original.labels);
+    }
+
+    #[test]
+    fn labels_turn_with_their_voxel() {
+        let turned = blueprint().transformed(1, false);
+        let label = &turned.labels[0];
+        let voxel = turned
+            .voxels
+            .iter()
+            .find(|voxel| voxel.direction == 3)
+            .expect("the labeled voxel faces -Z after a quarter turn");
+        assert_eq!(label.position, voxel.position);
+        assert_eq!(label.face, IVec3::NEG_Z);
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::{prelude::*, window::CursorGrabMode};
+use bevy_simple_text_input::{TextInputSubmitEvent, TextInputValue};
+
+use crate::prelude::*;
+
+/// Voxel face the label widget is editing.
+#[derive(Resource, Default)]
+pub struct LabelTarget {
+    pub face: Option<(IVec3, IVec3)>,
+}
+
+/// Unit offset of the face of `voxel` the crosshair is on.
+fn hit_face(voxel: &Voxel, ray_hit_pos: Vec3) -> IVec3 {
+    let offset = ray_hit_pos - voxel.position.as_vec3();
+    let axis = offset.abs().max_element();
+    if offset.x.abs() == axis {
+        IVec3::X * offset.x.signum() as i32
+    } else if offset.y.abs() == axis {
+        IVec3::Y * offset.y.signum() as i32
+    } else {
+        IVec3::Z * offset.z.signum() as i32
+    }
+}
+
+/// B opens the label widget for the face under the crosshair, prefilled with
+/// its label. Shift+B makes the labels of the hovered voxel show from every side,
+/// or again only from in front of their face.
+pub fn label_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    mut save: ResMut<SavedWorld>,
+    mut target: ResMut<LabelTarget>,
+    mut input_query: Query<&mut TextInputValue, With<LabelInput>>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    if *current_ui != GameUI::Default
+        || keyboard.pressed(KeyCode::ControlLeft)
+        || !keyboard.just_pressed(KeyCode::KeyB)
+    {
+        return;
+    }
+    let Some(voxel) = player.hit_voxel else {
+        return;
+    };
+
+    if keyboard.pressed(KeyCode::ShiftLeft) {
+        for label in save.labels.iter_mut().filter(|label| label.position == voxel.position) {
+            label.always_visible = !label.always_visible;
+        }
+        return;
+    }
+
+    let face = hit_face(&voxel, player.ray_hit_pos);
+    let text = save
+        .labels
+        .iter()
+        .find(|label| label.position == voxel.position && label.face == face)
+        .map_or(String::new(), |label| label.text.clone());
+    for mut value in input_query.iter_mut() {
+        value.0 = text.clone();
+    }
+    target.face = Some((voxel.position, face));
+
+    event_writer.send(GameEvent::UpdateCursorMode {
+        mode: CursorGrabMode::None,
+        show_cursor: true,
+        enable_input: false,
+    });
+    event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::LabelWidget });
+}
+
+/// Attaches the text typed into the label widget to the target face. An empty
+/// text removes the label.
+pub fn label_submit_system(
+    mut submit_events: EventReader<TextInputSubmitEvent>,
+    label_inputs: Query<(), With<LabelInput>>,
+    mut save: ResMut<SavedWorld>,
+    mut target: ResMut<LabelTarget>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    for event in submit_events.read() {
+        if !label_inputs.contains(event.entity) {
+            continue;
+        }
+        let Some((position, face)) = target.face.take() else {
+            continue;
+        };
+
+        let text: String = event.value.trim().chars().take(LABEL_MAX_LENGTH).collect();
+        let existing = save
+            .labels
+            .iter()
+            .position(|label| label.position == position && label.face == face);
+        match (existing, text.is_empty()) {
+            (Some(index), true) => {
+                save.labels.remove(index);
+            }
+            (Some(index), false) => save.labels[index].text = text,
+            (None, false) => save.labels.push(VoxelLabel {
+                position,
+                face,
+                text,
+                always_visible: false,
+            }),
+            (None, true) => {}
+        }
+
+        event_writer.send(GameEvent::UpdateCursorMode {
+            mode: CursorGrabMode::Locked,
+            show_cursor: false,
+            enable_input: true,
+        });
+        event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::Default });
+    }
+}
+
+/// Name of the signal at `position`: a label on the voxel itself, or else a
+/// label anywhere on a wire net running through it.
+pub fn signal_name<'a>(labels: &'a [VoxelLabel], voxel_map: &VoxelMap, position: IVec3) -> Option<&'a str> {
+    if let Some(label) = labels.iter().find(|label| label.position == position) {
+        return Some(&label.text);
+    }
+    let channels = match voxel_map.voxel_map.get(&position)?.kind {
+        VoxelType::Wire(ch) => ch..ch + 1,
+        VoxelType::BundledWire | VoxelType::WireCrossing => 0..u16::BITS as u8,
+        _ => return None,
+    };
+    channels.into_iter().find_map(|ch| {
+        let net = wire_net(voxel_map, position, ch);
+        labels
+            .iter()
+            .find(|label| net.contains(&label.position))
+            .map(|label| label.text.as_str())
+    })
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const LINE_COLOR: Color = Color::srgb(1.0, 1.0, 0.4);
+
+/// Run being dragged out with the line tool.
+#[derive(Resource, Default)]
+pub struct LineDrag {
+    pub start: Option<IVec3>,
+    pub end: IVec3,
+}
+
+impl LineDrag {
+    /// Cells from `start` to `end`: a straight run when they share two coordinates,
+    /// otherwise one leg per differing axis, longest leg first.
+    pub fn path(&self) -> Vec<IVec3> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+        let delta = self.end - start;
+        let mut axes = [IVec3::X, IVec3::Y, IVec3::Z];
+        axes.sort_by_key(|axis| -(delta * *axis).abs().max_element());
+
+        let mut path = vec![start];
+        let mut cursor = start;
+        for axis in axes {
+            let steps = (delta * axis).element_sum();
+            for _ in 0..steps.abs() {
+                cursor += axis * steps.signum();
+                path.push(cursor);
+            }
+        }
+        path
+    }
+}
+
+/// Line tool for drawing runs of the hotbar block.
+///
+/// L toggles the tool. Press the left button on the first cell, drag to the last
+/// one and release to place the whole run into the empty cells as one edit.
+/// Right click leaves the tool.
+pub fn line_tool_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut drag: ResMut<LineDrag>,
+    mut tool: ResMut<ActiveTool>,
+    mut gizmos: Gizmos,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    if *current_ui != GameUI::Default || keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyL) {
+        match *tool {
+            ActiveTool::Line => *tool = ActiveTool::None,
+            ActiveTool::None => *tool = ActiveTool::Line,
+            _ => {}
+        }
+        *drag = LineDrag::default();
+        return;
+    }
+    if *tool != ActiveTool::Line {
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Right) {
+        *tool = ActiveTool::None;
+        *drag = LineDrag::default();
+        return;
+    }
+
+    let target = player.selected_voxel.map(|voxel| voxel.position);
+    if mouse.just_pressed(MouseButton::Left) {
+        if let Some(position) = target {
+            *drag = LineDrag { start: Some(position), end: position };
+        }
+    } else if mouse.pressed(MouseButton::Left) {
+        // Keep the last end when the crosshair leaves reach
+        if let Some(position) = target {
+            drag.end = position;
+        }
+    }
+
+    let path = drag.path();
+    for position in &path {
+        gizmos.cuboid(
+            Transform::from_translation(position.as_vec3()).with_scale(Vec3::splat(0.9)),
+            LINE_COLOR,
+        );
+    }
+
+    if !mouse.just_released(MouseButton::Left) || drag.start.is_none() {
+        return;
+    }
+    *drag = LineDrag::default();
+
+    let kind = player.hotbar[player.hotbar_selector];
+    let direction = placement_direction(player.camera_dir, false);
+    let edits: Vec<(IVec3, Option<Voxel>)> = path
+        .into_iter()
+        .filter(|position| !voxel_map.voxel_map.contains_key(position))
+        .map(|position| (position, Some(fresh_voxel(kind, position, direction))))
+        .collect();
+    if edits.is_empty() {
+        return;
+    }
+
+    audio_writer.send(AudioEvent::World(WorldSfx::Place, edits[0].0));
+    apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+}
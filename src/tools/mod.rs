@@ -0,0 +1,10 @@
+pub mod selection;
+pub mod clipboard;
+pub mod array;
+pub mod symmetry;
+pub mod line;
+pub mod router;
+pub mod synth;
+pub mod chip;
+pub mod label;
+pub mod net_highlight;
\ No newline at end of file
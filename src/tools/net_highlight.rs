@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const NET_COLOR: Color = Color::srgb(1.0, 0.95, 0.3);
+const DRIVER_COLOR: Color = Color::srgb(1.0, 0.35, 0.25);
+const READER_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+
+/// The net under the crosshair, with the voxels driving and reading it.
+#[derive(Resource, Default)]
+pub struct NetHighlight {
+    pub cells: HashSet<IVec3>,
+    pub drivers: HashSet<IVec3>,
+    pub readers: HashSet<IVec3>,
+}
+
+impl NetHighlight {
+    /// Every channel net running through the cable `start`: one channel for
+    /// `Wire(n)`, all sixteen for a bundled wire.
+    pub fn trace(voxel_map: &VoxelMap, start: &Voxel) -> Self {
+        let channels = match start.kind {
+            VoxelType::Wire(ch) => ch..ch + 1,
+            VoxelType::BundledWire => 0..u16::BITS as u8,
+            _ => return Self::default(),
+        };
+        let cells: HashSet<IVec3> = channels
+            .flat_map(|ch| wire_net(voxel_map, start.position, ch))
+            .collect();
+
+        // Ports touching the net through a face it doesn't insulate
+        let touches = |voxel: &Voxel, ports: &[IVec3]| {
+            ports.iter().any(|port| {
+                cells.contains(port)
+                    && !voxel_map.voxel_map[port].is_insulated(voxel.position - *port)
+            })
+        };
+        let mut drivers = HashSet::new();
+        let mut readers = HashSet::new();
+        for cell in &cells {
+            for neighbor in get_neighboring_coords(*cell) {
+                let Some(voxel) = voxel_map.voxel_map.get(&neighbor) else {
+                    continue;
+                };
+                if matches!(
+                    voxel.kind,
+                    VoxelType::Wire(_) | VoxelType::BundledWire | VoxelType::WireCrossing
+                ) {
+                    continue;
+                }
+                let (inputs, outputs) = voxel_directions(voxel);
+                if touches(voxel, &outputs) {
+                    drivers.insert(neighbor);
+                }
+                if touches(voxel, &inputs) {
+                    readers.insert(neighbor);
+                }
+            }
+        }
+
+        Self {
+            cells,
+            drivers,
+            readers,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+/// Outlines the whole net of the wire under the crosshair, per channel, with
+/// the gates driving it and the gates reading it.
+pub fn net_highlight_system(
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut highlight: ResMut<NetHighlight>,
+    mut traced: Local<(Option<IVec3>, u64)>,
+    mut gizmos: Gizmos,
+) {
+    let hovered = player
+        .hit_voxel
+        .filter(|_| *current_ui == GameUI::Default)
+        .filter(|voxel| matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire));
+
+    // Retrace when the crosshair moves to another cell or the wiring is edited;
+    // signal changes alone leave the net as it is
+    let position = hovered.map(|voxel| voxel.position);
+    if (position, voxel_map.generation) != *traced {
+        *traced = (position, voxel_map.generation);
+        *highlight = hovered.map_or_else(NetHighlight::default, |voxel| NetHighlight::trace(&voxel_map, &voxel));
+    }
+
+    for (cells, scale, color) in [
+        (&highlight.cells, 1.02, NET_COLOR),
+        (&highlight.drivers, 1.06, DRIVER_COLOR),
+        (&highlight.readers, 1.06, READER_COLOR),
+    ] {
+        for cell in cells {
+            gizmos.cuboid(
+                Transform::from_translation(cell.as_vec3()).with_scale(Vec3::splat(scale)),
+                color,
+            );
+        }
+    }
+}
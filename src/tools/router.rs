@@ -0,0 +1,300 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Lowest layer of cells, resting on the ground.
+const GROUND_LAYER: i32 = 1;
+const STEP_COST: u32 = 10;
+/// Extra cost of a step that leaves the preferred layer.
+const OFF_LAYER_COST: u32 = 15;
+/// Extra cost of changing direction, so runs come out straight where they can.
+const TURN_COST: u32 = 1;
+/// How far the search may stray outside the box around both pins.
+const ROUTE_MARGIN: i32 = 8;
+
+const ROUTE_COLOR: Color = Color::srgb(0.3, 1.0, 0.9);
+
+/// State of the auto-router tool.
+#[derive(Resource, Default)]
+pub struct Router {
+    /// Gate whose output the route starts from, and the empty cell in front of that output.
+    pub source: Option<(IVec3, IVec3)>,
+    pub channel: u8,
+    /// Layer (y) the route should stay on where it can.
+    pub layer: Option<i32>,
+}
+
+/// Rules that keep a route from joining anything but its own net and pins.
+pub struct RouteRules<'a> {
+    pub voxel_map: &'a VoxelMap,
+    /// Voxels laid out but not placed yet, looked up before the voxel map.
+    pub planned: &'a HashMap<IVec3, Voxel>,
+    pub channel: u8,
+    /// The gates being connected.
+    pub ends: Vec<IVec3>,
+    /// Their port cells the route may use: the start cell and the goal cells.
+    pub pins: HashSet<IVec3>,
+    /// Wire cells of the net being extended, which the route may touch.
+    pub net: HashSet<IVec3>,
+}
+
+impl RouteRules<'_> {
+    fn voxel(&self, cell: &IVec3) -> Option<&Voxel> {
+        self.planned.get(cell).or_else(|| self.voxel_map.voxel_map.get(cell))
+    }
+
+    fn conducts_channel(&self, kind: VoxelType) -> bool {
+        match kind {
+            VoxelType::Wire(channel) => channel == self.channel,
+            kind => kind.uses_cable_mesh(),
+        }
+    }
+
+    /// Whether a wire of the route may sit in `cell`.
+    pub fn allows(&self, cell: IVec3) -> bool {
+        if cell.y < GROUND_LAYER || self.voxel(&cell).is_some() {
+            return false;
+        }
+        get_neighboring_coords(cell).iter().all(|neighbor| {
+            let Some(voxel) = self.voxel(neighbor) else {
+                return true;
+            };
+            if self.net.contains(neighbor) {
+                return true;
+            }
+            if self.conducts_channel(voxel.kind) {
+                return false; // would merge into another net
+            }
+            if self.ends.contains(neighbor) && self.pins.contains(&cell) {
+                return true;
+            }
+            // Stay clear of every port, which would drive or read the route
+            let (inputs, outputs) = voxel_directions(voxel);
+            !inputs.contains(&cell) && !outputs.contains(&cell)
+        })
+    }
+}
+
+/// Cheapest run of empty cells from one of `starts` to any of `goals` (both ends
+/// included), searched with A* inside a box around the pins.
+pub fn find_route(
+    rules: &RouteRules,
+    starts: &[IVec3],
+    goals: &[IVec3],
+    layer: Option<i32>,
+) -> Option<Vec<IVec3>> {
+    let first = *starts.first()?;
+    let (mut min, mut max) = (first, first);
+    for goal in starts.iter().chain(goals) {
+        min = min.min(*goal);
+        max = max.max(*goal);
+    }
+    min -= IVec3::splat(ROUTE_MARGIN);
+    max += IVec3::splat(ROUTE_MARGIN);
+
+    let heuristic = |cell: IVec3| {
+        goals
+            .iter()
+            .map(|goal| (*goal - cell).abs().element_sum() as u32 * STEP_COST)
+            .min()
+            .unwrap_or(0)
+    };
+
+    // Nodes remember the step that entered them so turns can be charged
+    let mut open = BinaryHeap::new();
+    let mut best: HashMap<(IVec3, IVec3), u32> = HashMap::new();
+    let mut came_from: HashMap<(IVec3, IVec3), (IVec3, IVec3)> = HashMap::new();
+    for &start in starts {
+        best.insert((start, IVec3::ZERO), 0);
+        open.push(Reverse((heuristic(start), 0, start.to_array(), IVec3::ZERO.to_array())));
+    }
+
+    while let Some(Reverse((_, cost, cell, step))) = open.pop() {
+        let node = (IVec3::from_array(cell), IVec3::from_array(step));
+        if best.get(&node).is_some_and(|&known| known < cost) {
+            continue;
+        }
+        if goals.contains(&node.0) {
+            let mut path = vec![node.0];
+            let mut current = node;
+            while let Some(&previous) = came_from.get(&current) {
+                path.push(previous.0);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for next in get_neighboring_coords(node.0) {
+            if next.cmplt(min).any() || next.cmpgt(max).any() || !rules.allows(next) {
+                continue;
+            }
+            let next_step = next - node.0;
+            let mut next_cost = cost + STEP_COST;
+            if node.1 != IVec3::ZERO && next_step != node.1 {
+                next_cost += TURN_COST;
+            }
+            if layer.is_some_and(|layer| next.y != layer) {
+                next_cost += OFF_LAYER_COST;
+            }
+
+            let next_node = (next, next_step);
+            if best.get(&next_node).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+            best.insert(next_node, next_cost);
+            came_from.insert(next_node, node);
+            open.push(Reverse((
+                next_cost + heuristic(next),
+                next_cost,
+                next.to_array(),
+                next_step.to_array(),
+            )));
+        }
+    }
+    None
+}
+
+/// Output cell of `voxel` closest to `point`.
+fn nearest_output(voxel: &Voxel, point: Vec3) -> Option<IVec3> {
+    let (_, outputs) = voxel_directions(voxel);
+    outputs
+        .into_iter()
+        .min_by(|a, b| a.as_vec3().distance(point).total_cmp(&b.as_vec3().distance(point)))
+}
+
+/// Auto-router between a gate output and a gate input.
+///
+/// P toggles the tool. Left click a gate to start from its output nearest the
+/// crosshair, then left click the gate to feed; the route is laid as
+/// `Wire(channel)` through empty cells without touching other nets of that
+/// channel or other ports. = / - change the channel, ] / [ raise and lower the
+/// preferred layer and 0 drops the preference. Right click cancels the source,
+/// or leaves the tool when none is picked.
+pub fn router_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut router: ResMut<Router>,
+    mut tool: ResMut<ActiveTool>,
+    mut message: ResMut<ToolMessage>,
+    mut gizmos: Gizmos,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    if *current_ui != GameUI::Default || keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyP) {
+        match *tool {
+            ActiveTool::Route => *tool = ActiveTool::None,
+            ActiveTool::None => *tool = ActiveTool::Route,
+            _ => {}
+        }
+        router.source = None;
+        return;
+    }
+    if *tool != ActiveTool::Route {
+        return;
+    }
+
+    let channels = u16::BITS as u8;
+    if keyboard.just_pressed(KeyCode::Equal) {
+        router.channel = (router.channel + 1) % channels;
+    }
+    if keyboard.just_pressed(KeyCode::Minus) {
+        router.channel = (router.channel + channels - 1) % channels;
+    }
+    let base_layer = router
+        .source
+        .map_or(player.camera_pos.y.round() as i32, |(_, start)| start.y);
+    if keyboard.just_pressed(KeyCode::BracketRight) {
+        router.layer = Some(router.layer.map_or(base_layer, |layer| layer + 1));
+    }
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        router.layer = Some(router.layer.map_or(base_layer, |layer| (layer - 1).max(GROUND_LAYER)));
+    }
+    if keyboard.just_pressed(KeyCode::Digit0) {
+        router.layer = None;
+    }
+
+    if let Some((_, start)) = router.source {
+        gizmos.cuboid(
+            Transform::from_translation(start.as_vec3()).with_scale(Vec3::splat(0.5)),
+            ROUTE_COLOR,
+        );
+    }
+
+    if mouse.just_pressed(MouseButton::Right) {
+        if router.source.take().is_none() {
+            *tool = ActiveTool::None;
+        }
+        return;
+    }
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(gate) = player.hit_voxel else {
+        return;
+    };
+
+    let Some((source, start)) = router.source else {
+        // First click picks the output
+        match nearest_output(&gate, player.ray_hit_pos) {
+            Some(start) if !voxel_map.voxel_map.contains_key(&start) => {
+                router.source = Some((gate.position, start));
+            }
+            Some(_) => message.show(format!("Output of {:?} is already connected", gate.kind)),
+            None => {}
+        }
+        return;
+    };
+
+    // Second click routes into every free input of the destination
+    let (inputs, _) = voxel_directions(&gate);
+    let goals: Vec<IVec3> = inputs
+        .into_iter()
+        .filter(|cell| !voxel_map.voxel_map.contains_key(cell))
+        .collect();
+    if goals.is_empty() || gate.position == source {
+        return;
+    }
+
+    let mut pins: HashSet<IVec3> = goals.iter().copied().collect();
+    pins.insert(start);
+    let planned = HashMap::new();
+    let rules = RouteRules {
+        voxel_map: &voxel_map,
+        planned: &planned,
+        channel: router.channel,
+        ends: vec![source, gate.position],
+        pins,
+        net: HashSet::new(),
+    };
+    let route = if rules.allows(start) {
+        find_route(&rules, &[start], &goals, router.layer)
+    } else {
+        None
+    };
+    let Some(route) = route else {
+        message.show(format!("No route from {start} to {}", gate.position));
+        return;
+    };
+
+    router.source = None;
+    let wire = VoxelType::Wire(router.channel);
+    let edits: Vec<(IVec3, Option<Voxel>)> = route
+        .into_iter()
+        .map(|position| (position, Some(fresh_voxel(wire, position, 1))))
+        .collect();
+    audio_writer.send(AudioEvent::World(WorldSfx::Place, start));
+    apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+}
@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const SELECTION_COLOR: Color = Color::srgb(0.2, 0.8, 1.0);
+const CORNER_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Box of cells marked with G. Corners are set alternately, the first one again
+/// after both are placed.
+#[derive(Resource, Default)]
+pub struct Selection {
+    pub corners: [Option<IVec3>; 2],
+    next_corner: usize,
+}
+
+impl Selection {
+    /// Lowest and highest cell of the box, once both corners are set.
+    pub fn bounds(&self) -> Option<(IVec3, IVec3)> {
+        let [Some(a), Some(b)] = self.corners else {
+            return None;
+        };
+        Some((a.min(b), a.max(b)))
+    }
+
+    /// Every cell inside the box, in x / y / z order.
+    pub fn positions(&self) -> Vec<IVec3> {
+        let Some((min, max)) = self.bounds() else {
+            return Vec::new();
+        };
+        let mut positions = Vec::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    positions.push(IVec3::new(x, y, z));
+                }
+            }
+        }
+        positions
+    }
+
+    pub fn volume(&self) -> i64 {
+        self.bounds().map_or(0, |(min, max)| {
+            let size = (max - min + IVec3::ONE).as_i64vec3();
+            size.x * size.y * size.z
+        })
+    }
+
+    /// Fails with the message shown to the player when the box is too large
+    /// for region operations (`SELECTION_MAX_VOLUME`).
+    pub fn check_volume(&self) -> Result<(), String> {
+        let volume = self.volume();
+        if volume > SELECTION_MAX_VOLUME {
+            return Err(format!("Selection too large: {volume} cells, at most {SELECTION_MAX_VOLUME}"));
+        }
+        Ok(())
+    }
+
+    fn mark(&mut self, position: IVec3) {
+        self.corners[self.next_corner] = Some(position);
+        self.next_corner = 1 - self.next_corner;
+    }
+
+    fn clear(&mut self) {
+        *self = Selection::default();
+    }
+}
+
+/// Sends the events that turn the cells in `edits` into the given voxels (or empty
+/// cells for `None`), followed by the mesh updates of everything around them.
+///
+/// Used by every bulk tool so the changes run through `event_handler` like a
+/// placement by hand, and land in the edit history as a single step.
+pub fn apply_bulk_edit(
+    edits: &[(IVec3, Option<Voxel>)],
+    voxel_map: &VoxelMap,
+    event_writer: &mut EventWriter<GameEvent>,
+) {
+    for (position, voxel) in edits {
+        if voxel_map.voxel_map.contains_key(position) {
+            event_writer.send(GameEvent::RemoveBlock { position: *position });
+        }
+        let Some(voxel) = voxel else {
+            continue;
+        };
+        let Some(voxel_asset) = voxel_map.asset(voxel.kind).cloned() else {
+            continue;
+        };
+        event_writer.send(GameEvent::PlaceBlock {
+            voxel: *voxel,
+            voxel_asset,
+        });
+    }
+    for (position, _) in edits {
+        event_writer.send(GameEvent::UpdateMesh {
+            updates: get_neighboring_coords(*position),
+        });
+    }
+}
+
+/// Marks the selection corners and runs the region operations on it.
+///
+/// G marks a corner on the hovered voxel (or the empty cell in front of the
+/// crosshair), X drops the selection. With a box selected, Enter fills its empty
+/// cells with the hotbar block, Delete clears it and T replaces every voxel of
+/// the hovered kind with the hotbar block.
+pub fn selection_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut selection: ResMut<Selection>,
+    mut gizmos: Gizmos,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    draw_selection(&selection, &mut gizmos);
+
+    if *current_ui != GameUI::Default || keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        let target = player
+            .hit_voxel
+            .or(player.selected_voxel)
+            .map(|voxel| voxel.position);
+        if let Some(position) = target {
+            selection.mark(position);
+        }
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyX) {
+        selection.clear();
+        return;
+    }
+
+    let Some((min, max)) = selection.bounds() else {
+        return;
+    };
+    // The tool readout says why
+    if selection.check_volume().is_err() {
+        return;
+    }
+    let center = (min + max) / 2;
+    let hotbar_kind = player.hotbar[player.hotbar_selector];
+
+    let edits: Vec<(IVec3, Option<Voxel>)> = if keyboard.just_pressed(KeyCode::Enter) {
+        let direction = placement_direction(player.camera_dir, false);
+        selection
+            .positions()
+            .into_iter()
+            .filter(|position| !voxel_map.voxel_map.contains_key(position))
+            .map(|position| (position, Some(fresh_voxel(hotbar_kind, position, direction))))
+            .collect()
+    } else if keyboard.just_pressed(KeyCode::Delete) {
+        selection
+            .positions()
+            .into_iter()
+            .filter(|position| voxel_map.voxel_map.contains_key(position))
+            .map(|position| (position, None))
+            .collect()
+    } else if keyboard.just_pressed(KeyCode::KeyT) {
+        let Some(from) = player.hit_voxel.map(|voxel| voxel.kind) else {
+            return;
+        };
+        selection
+            .positions()
+            .into_iter()
+            .filter_map(|position| voxel_map.voxel_map.get(&position))
+            .filter(|voxel| voxel.kind == from)
+            .map(|voxel| {
+                let replacement = fresh_voxel(hotbar_kind, voxel.position, voxel.direction);
+                (voxel.position, Some(replacement))
+            })
+            .collect()
+    } else {
+        return;
+    };
+    if edits.is_empty() {
+        return;
+    }
+
+    let sfx = if edits.iter().all(|(_, voxel)| voxel.is_none()) {
+        WorldSfx::Destroy
+    } else {
+        WorldSfx::Place
+    };
+    audio_writer.send(AudioEvent::World(sfx, center));
+    apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+}
+
+fn draw_selection(selection: &Selection, gizmos: &mut Gizmos) {
+    for corner in selection.corners.iter().flatten() {
+        gizmos.cuboid(
+            Transform::from_translation(corner.as_vec3()).with_scale(Vec3::splat(1.02)),
+            CORNER_COLOR,
+        );
+    }
+    if let Some((min, max)) = selection.bounds() {
+        let center = (min + max).as_vec3() / 2.0;
+        let size = (max - min + IVec3::ONE).as_vec3();
+        gizmos.cuboid(
+            Transform::from_translation(center).with_scale(size + Vec3::splat(0.04)),
+            SELECTION_COLOR,
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const PLANE_COLOR: Color = Color::srgba(1.0, 0.4, 0.3, 0.6);
+const PLANE_CELLS: u32 = 24;
+
+/// Mirror planes of the symmetry build mode, at most one per horizontal axis.
+/// A plane runs through the centre of its cell, so voxels on it map onto themselves.
+#[derive(Resource, Default)]
+pub struct Symmetry {
+    /// X coordinate of the plane with normal X.
+    pub x: Option<i32>,
+    /// Z coordinate of the plane with normal Z.
+    pub z: Option<i32>,
+}
+
+impl Symmetry {
+    pub fn is_active(&self) -> bool {
+        self.x.is_some() || self.z.is_some()
+    }
+
+    /// Mirror images of a voxel placed at `position` facing `direction`, without
+    /// the original and without duplicates for cells on a plane.
+    pub fn images(&self, position: IVec3, direction: u8) -> Vec<(IVec3, u8)> {
+        let mut images = vec![(position, direction)];
+        if let Some(x) = self.x {
+            for i in 0..images.len() {
+                let (p, d) = images[i];
+                images.push((IVec3::new(2 * x - p.x, p.y, p.z), mirrored_direction(d, IVec3::X)));
+            }
+        }
+        if let Some(z) = self.z {
+            for i in 0..images.len() {
+                let (p, d) = images[i];
+                images.push((IVec3::new(p.x, p.y, 2 * z - p.z), mirrored_direction(d, IVec3::Z)));
+            }
+        }
+
+        let mut unique: Vec<(IVec3, u8)> = Vec::new();
+        for image in images.into_iter().skip(1) {
+            if image.0 != position && !unique.iter().any(|(p, _)| *p == image.0) {
+                unique.push(image);
+            }
+        }
+        unique
+    }
+}
+
+/// Sets the symmetry planes and draws them.
+///
+/// Y puts a plane through the hovered cell across the player's left / right
+/// axis, replacing the plane on that axis; Shift+Y removes every plane.
+pub fn symmetry_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_ui: Res<GameUI>,
+    player: Res<Player>,
+    mut symmetry: ResMut<Symmetry>,
+    mut gizmos: Gizmos,
+) {
+    draw_planes(&symmetry, player.camera_pos, &mut gizmos);
+
+    if *current_ui != GameUI::Default
+        || keyboard.pressed(KeyCode::ControlLeft)
+        || !keyboard.just_pressed(KeyCode::KeyY)
+    {
+        return;
+    }
+    if keyboard.pressed(KeyCode::ShiftLeft) {
+        *symmetry = Symmetry::default();
+        return;
+    }
+
+    let Some(position) = player
+        .hit_voxel
+        .or(player.selected_voxel)
+        .map(|voxel| voxel.position)
+    else {
+        return;
+    };
+    // Looking along Z splits the world into left and right along X, and vice versa
+    if player.camera_dir.z.abs() >= player.camera_dir.x.abs() {
+        symmetry.x = Some(position.x);
+    } else {
+        symmetry.z = Some(position.z);
+    }
+}
+
+/// Grids are offset half a cell so their lines follow the voxel boundaries.
+fn draw_planes(symmetry: &Symmetry, around: Vec3, gizmos: &mut Gizmos) {
+    let cells = UVec2::splat(PLANE_CELLS);
+    if let Some(x) = symmetry.x {
+        let center = Vec3::new(x as f32, around.y.round() + 0.5, around.z.round() + 0.5);
+        let rotation = Quat::from_rotation_y(FRAC_PI_2);
+        gizmos.grid(Isometry3d::new(center, rotation), cells, Vec2::ONE, PLANE_COLOR);
+    }
+    if let Some(z) = symmetry.z {
+        let center = Vec3::new(around.x.round() + 0.5, around.y.round() + 0.5, z as f32);
+        gizmos.grid(Isometry3d::new(center, Quat::IDENTITY), cells, Vec2::ONE, PLANE_COLOR);
+    }
+}
@@ -0,0 +1,681 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use bevy::{prelude::*, window::CursorGrabMode};
+use bevy_simple_text_input::TextInputSubmitEvent;
+
+use crate::prelude::*;
+
+/// Cells between neighbouring switches / gates across the layout.
+const COLUMN_SPACING: i32 = 4;
+/// Cells between the rows of gates along the signal flow.
+const ROW_SPACING: i32 = 5;
+/// How often the layout is pushed further ahead when its footprint is not free.
+const PLACEMENT_TRIES: i32 = 8;
+const MAX_INPUTS: usize = 8;
+const MAX_GATES: usize = 64;
+
+/// Boolean expression over inputs numbered in alphabetical order of their names.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Input(usize),
+    Const(bool),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Xor(Vec<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Const(bool),
+    Not,
+    And,
+    Or,
+    Xor,
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '!' | '~' => Token::Not,
+            '&' | '*' => Token::And,
+            '|' | '+' => Token::Or,
+            '^' => Token::Xor,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '0' => Token::Const(false),
+            '1' => Token::Const(true),
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Name(name));
+                continue;
+            }
+            c => return Err(format!("unexpected '{c}'")),
+        };
+        chars.next();
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over `|` < `^` < `&` < `!`, collecting runs of one operator
+/// into a single n-ary node.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    names: &'a [String],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn binary(
+        &mut self,
+        operator: Token,
+        operand: fn(&mut Self) -> Result<Expr, String>,
+        node: fn(Vec<Expr>) -> Expr,
+    ) -> Result<Expr, String> {
+        let mut operands = vec![operand(self)?];
+        while self.peek() == Some(&operator) {
+            self.position += 1;
+            operands.push(operand(self)?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { node(operands) })
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        self.binary(Token::Or, Self::xor, Expr::Or)
+    }
+
+    fn xor(&mut self) -> Result<Expr, String> {
+        self.binary(Token::Xor, Self::and, Expr::Xor)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        self.binary(Token::And, Self::unary, Expr::And)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let token = self.peek().cloned().ok_or("expression ends too early")?;
+        self.position += 1;
+        match token {
+            Token::Not => Ok(Expr::Not(Box::new(self.unary()?))),
+            Token::Const(value) => Ok(Expr::Const(value)),
+            Token::Name(name) => Ok(Expr::Input(
+                self.names.iter().position(|n| *n == name).expect("names come from the tokens"),
+            )),
+            Token::Open => {
+                let inner = self.or()?;
+                match self.peek() {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Ok(inner)
+                    }
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            token => Err(format!("unexpected {token:?}")),
+        }
+    }
+}
+
+/// Parses an expression like `(A & !B) | C`. Returns it with the input names
+/// in alphabetical order, which is also the order of the input switches.
+pub fn parse_expression(text: &str) -> Result<(Expr, Vec<String>), String> {
+    let tokens = tokenize(text)?;
+    let names: Vec<String> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Name(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut parser = Parser { tokens: &tokens, position: 0, names: &names };
+    let expr = parser.or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {token:?}"));
+    }
+    Ok((expr, names))
+}
+
+/// Parses a truth table such as `00 0; 01 1; 10 1; 11 0`: one row per input
+/// combination, the last digit being the output. Rows left out are 0. The result
+/// is the sum of the rows that output 1, over inputs named A, B, C, ...
+pub fn parse_truth_table(text: &str) -> Result<(Expr, Vec<String>), String> {
+    let rows: Vec<Vec<bool>> = text
+        .split([';', ',', '\n'])
+        .map(|row| row.chars().filter(|c| c.is_ascii_digit()).map(|c| c == '1').collect::<Vec<bool>>())
+        .filter(|row| !row.is_empty())
+        .collect();
+    let width = rows.first().map_or(0, Vec::len);
+    if width < 2 || rows.iter().any(|row| row.len() != width) {
+        return Err("every row needs the same inputs plus one output".to_string());
+    }
+    let inputs = width - 1;
+    if inputs > MAX_INPUTS {
+        return Err(format!("at most {MAX_INPUTS} inputs"));
+    }
+
+    let minterms: Vec<Expr> = rows
+        .iter()
+        .filter(|row| row[inputs])
+        .map(|row| {
+            Expr::And(
+                (0..inputs)
+                    .map(|i| if row[i] { Expr::Input(i) } else { Expr::Not(Box::new(Expr::Input(i))) })
+                    .collect(),
+            )
+        })
+        .collect();
+    let names = (0..inputs).map(|i| ((b'A' + i as u8) as char).to_string()).collect();
+    Ok((Expr::Or(minterms), names))
+}
+
+fn is_truth_table(text: &str) -> bool {
+    text.chars().all(|c| "01:=;, \t\r\n".contains(c))
+        && text.chars().filter(char::is_ascii_digit).count() >= 2
+}
+
+/// Folds constants, double negations and nested runs of the same operator.
+pub fn simplify(expr: Expr) -> Expr {
+    fn operands(list: Vec<Expr>, same: fn(&Expr) -> Option<&Vec<Expr>>) -> Vec<Expr> {
+        let mut flat = Vec::new();
+        for operand in list.into_iter().map(simplify) {
+            match same(&operand) {
+                Some(inner) => flat.extend(inner.iter().cloned()),
+                None => flat.push(operand),
+            }
+        }
+        flat
+    }
+    fn single(mut list: Vec<Expr>, node: fn(Vec<Expr>) -> Expr) -> Expr {
+        if list.len() == 1 { list.remove(0) } else { node(list) }
+    }
+
+    match expr {
+        Expr::Not(inner) => match simplify(*inner) {
+            Expr::Const(value) => Expr::Const(!value),
+            Expr::Not(inner) => *inner,
+            inner => Expr::Not(Box::new(inner)),
+        },
+        Expr::And(list) => {
+            let list = operands(list, |e| if let Expr::And(l) = e { Some(l) } else { None });
+            if list.contains(&Expr::Const(false)) {
+                return Expr::Const(false);
+            }
+            let list: Vec<Expr> = list.into_iter().filter(|e| *e != Expr::Const(true)).collect();
+            if list.is_empty() { Expr::Const(true) } else { single(list, Expr::And) }
+        }
+        Expr::Or(list) => {
+            let list = operands(list, |e| if let Expr::Or(l) = e { Some(l) } else { None });
+            if list.contains(&Expr::Const(true)) {
+                return Expr::Const(true);
+            }
+            let list: Vec<Expr> = list.into_iter().filter(|e| *e != Expr::Const(false)).collect();
+            if list.is_empty() { Expr::Const(false) } else { single(list, Expr::Or) }
+        }
+        Expr::Xor(list) => {
+            let list = operands(list, |e| if let Expr::Xor(l) = e { Some(l) } else { None });
+            let parity = list.iter().filter(|e| **e == Expr::Const(true)).count() % 2 == 1;
+            let list: Vec<Expr> = list.into_iter().filter(|e| !matches!(e, Expr::Const(_))).collect();
+            if list.is_empty() {
+                return Expr::Const(parity);
+            }
+            let xor = single(list, Expr::Xor);
+            if parity { simplify(Expr::Not(Box::new(xor))) } else { xor }
+        }
+        expr => expr,
+    }
+}
+
+/// Where a gate input or the output light takes its signal from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Source {
+    Input(usize),
+    Gate(usize),
+}
+
+struct Gate {
+    kind: VoxelType,
+    inputs: Vec<Source>,
+    level: i32,
+}
+
+/// Gate network for an expression, using the 2- and 3-input gates and folding
+/// a negated 2-input gate into its Nand / Nor / Xnor variant.
+#[derive(Default)]
+struct Netlist {
+    gates: Vec<Gate>,
+}
+
+impl Netlist {
+    fn level(&self, source: Source) -> i32 {
+        match source {
+            Source::Input(_) => 0,
+            Source::Gate(gate) => self.gates[gate].level,
+        }
+    }
+
+    fn add(&mut self, kind: VoxelType, inputs: Vec<Source>) -> Source {
+        let level = 1 + inputs.iter().map(|&s| self.level(s)).max().unwrap_or(0);
+        self.gates.push(Gate { kind, inputs, level });
+        Source::Gate(self.gates.len() - 1)
+    }
+
+    fn build(&mut self, expr: &Expr) -> Result<Source, String> {
+        use VoxelType::*;
+        Ok(match expr {
+            Expr::Input(input) => Source::Input(*input),
+            Expr::Const(_) => return Err("the expression is constant".to_string()),
+            Expr::Not(inner) => match inner.as_ref() {
+                Expr::And(list) if list.len() == 2 => self.pair(list, And(AndVariants::NandGate))?,
+                Expr::Or(list) if list.len() == 2 => self.pair(list, Or(OrVariants::NorGate))?,
+                Expr::Xor(list) if list.len() == 2 => self.pair(list, Xor(XorVariants::XnorGate))?,
+                inner => {
+                    let source = self.build(inner)?;
+                    self.add(Not(NotVariants::NotGate), vec![source])
+                }
+            },
+            Expr::And(list) => self.tree(list, And(AndVariants::AndGate), And(AndVariants::And3Gate))?,
+            Expr::Or(list) => self.tree(list, Or(OrVariants::OrGate), Or(OrVariants::Or3Gate))?,
+            Expr::Xor(list) => self.tree(list, Xor(XorVariants::XorGate), Xor(XorVariants::Xor3Gate))?,
+        })
+    }
+
+    fn pair(&mut self, list: &[Expr], kind: VoxelType) -> Result<Source, String> {
+        let inputs = list.iter().map(|e| self.build(e)).collect::<Result<Vec<_>, _>>()?;
+        Ok(self.add(kind, inputs))
+    }
+
+    /// Reduces an n-ary operator to a tree of 2- and 3-input gates.
+    fn tree(&mut self, list: &[Expr], two: VoxelType, three: VoxelType) -> Result<Source, String> {
+        let mut sources = list.iter().map(|e| self.build(e)).collect::<Result<Vec<_>, _>>()?;
+        while sources.len() > 1 {
+            let mut next = Vec::new();
+            let mut rest = sources.as_slice();
+            while !rest.is_empty() {
+                // Never leave a single operand behind
+                let size = match rest.len() {
+                    1 => 1,
+                    4 => 2,
+                    n => n.min(3),
+                };
+                let (chunk, tail) = rest.split_at(size);
+                next.push(match size {
+                    1 => chunk[0],
+                    2 => self.add(two, chunk.to_vec()),
+                    _ => self.add(three, chunk.to_vec()),
+                });
+                rest = tail;
+            }
+            sources = next;
+        }
+        Ok(sources[0])
+    }
+}
+
+/// Local layout frame: +Z is the way the signals flow, +X to the player's right,
+/// which is world -X when looking along +Z.
+fn to_world(local: IVec3, anchor: IVec3, turns: u8) -> IVec3 {
+    anchor + rotated_offset(local * IVec3::new(-1, 1, 1), turns)
+}
+
+/// Lays out the network in front of the player and routes every net with the
+/// auto-router, one wire channel per net. Returns every voxel to place.
+fn synthesize(
+    expr: &Expr,
+    names: &[String],
+    player: &Player,
+    voxel_map: &VoxelMap,
+) -> Result<(Vec<Voxel>, Vec<VoxelLabel>), String> {
+    let inputs = names.len();
+    if inputs == 0 {
+        return Err("the expression has no inputs".to_string());
+    }
+    if inputs > MAX_INPUTS {
+        return Err(format!("at most {MAX_INPUTS} inputs"));
+    }
+    let mut netlist = Netlist::default();
+    let root = netlist.build(expr)?;
+    if netlist.gates.len() > MAX_GATES {
+        return Err(format!("needs more than {MAX_GATES} gates"));
+    }
+
+    // --- local positions: switches in row 0, gates by level, light last ---
+    let depth = netlist.gates.iter().map(|gate| gate.level).max().unwrap_or(0);
+    let mut local: HashMap<Source, IVec3> = HashMap::new();
+    for input in 0..inputs {
+        local.insert(Source::Input(input), IVec3::new(input as i32 * COLUMN_SPACING, 0, 0));
+    }
+    let mut columns = vec![0; depth as usize + 1];
+    for (index, gate) in netlist.gates.iter().enumerate() {
+        let column = &mut columns[gate.level as usize];
+        local.insert(
+            Source::Gate(index),
+            IVec3::new(*column * COLUMN_SPACING, 0, gate.level * ROW_SPACING),
+        );
+        *column += 1;
+    }
+    let light_local = IVec3::new(local[&root].x, 0, (depth + 1) * ROW_SPACING);
+    let width = local.values().map(|p| p.x).max().unwrap_or(0);
+    let shift = IVec3::new(-width / 2, 0, 0);
+
+    // --- find a free spot ahead, facing the way the camera looks ---
+    let yaw = cardinalize(player.camera_dir);
+    let turns = yaw - 1;
+    let forward = rotated_offset(IVec3::Z, turns);
+    let base = player
+        .selected_voxel
+        .map(|voxel| voxel.position)
+        .ok_or("look at the ground where the circuit should go")?;
+    let footprint_min = shift - IVec3::new(1, 0, 1);
+    let footprint_max = shift + IVec3::new(width + 1, 2, light_local.z + 1);
+    let anchor = (0..PLACEMENT_TRIES)
+        .map(|step| base + forward * step * COLUMN_SPACING)
+        .find(|&anchor| {
+            (footprint_min.x..=footprint_max.x).all(|x| {
+                (footprint_min.y..=footprint_max.y).all(|y| {
+                    (footprint_min.z..=footprint_max.z).all(|z| {
+                        let cell = to_world(IVec3::new(x, y, z), anchor, turns);
+                        !voxel_map.voxel_map.contains_key(&cell)
+                    })
+                })
+            })
+        })
+        .ok_or("no free space in front of you")?;
+
+    // --- place switches, gates and the light ---
+    let mut planned: HashMap<IVec3, Voxel> = HashMap::new();
+    let mut place = |kind: VoxelType, local_position: IVec3| {
+        let position = to_world(local_position + shift, anchor, turns);
+        let voxel = fresh_voxel(kind, position, yaw);
+        planned.insert(position, voxel);
+        voxel
+    };
+    let mut drivers: HashMap<Source, Voxel> = HashMap::new();
+    for input in 0..inputs {
+        let source = Source::Input(input);
+        drivers.insert(source, place(VoxelType::Component(ComponentVariants::Switch), local[&source]));
+    }
+    let mut gate_voxels = Vec::new();
+    for (index, gate) in netlist.gates.iter().enumerate() {
+        let source = Source::Gate(index);
+        let voxel = place(gate.kind, local[&source]);
+        drivers.insert(source, voxel);
+        gate_voxels.push(voxel);
+    }
+    let light = place(VoxelType::Component(ComponentVariants::Light), light_local);
+
+    // Switches carry their variable name and the light "out", on the side facing the player
+    let label = |voxel: &Voxel, text: &str| VoxelLabel {
+        position: voxel.position,
+        face: -forward,
+        text: text.to_string(),
+        always_visible: false,
+    };
+    let mut labels: Vec<VoxelLabel> = names
+        .iter()
+        .enumerate()
+        .map(|(input, name)| label(&drivers[&Source::Input(input)], name))
+        .collect();
+    labels.push(label(&light, "out"));
+
+    // --- nets: every driver with the input cells it feeds ---
+    let mut sinks: HashMap<Source, Vec<(IVec3, IVec3)>> = HashMap::new();
+    for (gate, voxel) in netlist.gates.iter().zip(&gate_voxels) {
+        let (cells, _) = voxel_directions(voxel);
+        for (source, cell) in gate.inputs.iter().zip(cells) {
+            sinks.entry(*source).or_default().push((voxel.position, cell));
+        }
+    }
+    let (light_inputs, _) = voxel_directions(&light);
+    sinks.entry(root).or_default().push((light.position, light_inputs[0]));
+
+    let mut order: Vec<Source> = sinks.keys().copied().collect();
+    order.sort_by_key(|source| match source {
+        Source::Input(input) => (0, *input),
+        Source::Gate(gate) => (1, *gate),
+    });
+
+    for (net_index, source) in order.into_iter().enumerate() {
+        let driver = drivers[&source];
+        let channel = (net_index % u16::BITS as usize) as u8;
+        let start = voxel_directions(&driver).1[0];
+        let mut net_sinks = sinks.remove(&source).unwrap_or_default();
+        net_sinks.sort_by_key(|(_, cell)| (*cell - start).abs().element_sum());
+
+        let mut ends = vec![driver.position];
+        ends.extend(net_sinks.iter().map(|(gate, _)| *gate));
+        let mut pins: HashSet<IVec3> = net_sinks.iter().map(|(_, cell)| *cell).collect();
+        pins.insert(start);
+
+        let mut net: HashSet<IVec3> = HashSet::new();
+        for (_, goal) in net_sinks {
+            if net.contains(&goal) {
+                continue; // an earlier branch already runs through it
+            }
+            let rules = RouteRules {
+                voxel_map,
+                planned: &planned,
+                channel,
+                ends: ends.clone(),
+                pins: pins.clone(),
+                net: net.clone(),
+            };
+            let starts: Vec<IVec3> = if net.is_empty() {
+                if !rules.allows(start) {
+                    return Err("an output is blocked".to_string());
+                }
+                vec![start]
+            } else {
+                net.iter().copied().collect()
+            };
+            let route = find_route(&rules, &starts, &[goal], None).ok_or("couldn't wire the gates")?;
+            for cell in route {
+                planned.entry(cell).or_insert_with(|| fresh_voxel(VoxelType::Wire(channel), cell, 1));
+                net.insert(cell);
+            }
+        }
+    }
+
+    Ok((planned.into_values().collect(), labels))
+}
+
+/// Builds the circuit typed into the synthesis widget (F4): an expression like
+/// `(A & !B) | C` or a truth table like `00 0; 01 1; 10 1; 11 0`. Inputs become
+/// switches ordered by name from left to right and labeled with it, the result
+/// drives a light labeled "out".
+pub fn synth_submit_system(
+    mut submit_events: EventReader<TextInputSubmitEvent>,
+    synth_inputs: Query<(), With<SynthInput>>,
+    mut status_query: Query<&mut Text, With<SynthStatusText>>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    mut save: ResMut<SavedWorld>,
+    mut event_writer: EventWriter<GameEvent>,
+    mut audio_writer: EventWriter<AudioEvent>,
+) {
+    for event in submit_events.read() {
+        if !synth_inputs.contains(event.entity) {
+            continue;
+        }
+        let parsed = if is_truth_table(&event.value) {
+            parse_truth_table(&event.value)
+        } else {
+            parse_expression(&event.value)
+        };
+        let result = parsed.and_then(|(expr, names)| synthesize(&simplify(expr), &names, &player, &voxel_map));
+
+        let (voxels, labels) = match result {
+            Ok(built) => built,
+            Err(error) => {
+                for mut text in status_query.iter_mut() {
+                    text.0 = format!("Error: {error}");
+                }
+                continue;
+            }
+        };
+
+        for mut text in status_query.iter_mut() {
+            text.0.clear();
+        }
+        save.labels.extend(labels);
+        let edits: Vec<(IVec3, Option<Voxel>)> =
+            voxels.into_iter().map(|voxel| (voxel.position, Some(voxel))).collect();
+        if let Some((position, _)) = edits.first() {
+            audio_writer.send(AudioEvent::World(WorldSfx::Place, *position));
+        }
+        apply_bulk_edit(&edits, &voxel_map, &mut event_writer);
+
+        event_writer.send(GameEvent::UpdateCursorMode {
+            mode: CursorGrabMode::Locked,
+            show_cursor: false,
+            enable_input: true,
+        });
+        event_writer.send(GameEvent::ToggleUI { new_ui: GameUI::Default });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(i: usize) -> Expr {
+        Expr::Input(i)
+    }
+
+    fn not(expr: Expr) -> Expr {
+        Expr::Not(Box::new(expr))
+    }
+
+    fn eval(expr: &Expr, inputs: &[bool]) -> bool {
+        match expr {
+            Expr::Input(i) => inputs[*i],
+            Expr::Const(value) => *value,
+            Expr::Not(inner) => !eval(inner, inputs),
+            Expr::And(list) => list.iter().all(|e| eval(e, inputs)),
+            Expr::Or(list) => list.iter().any(|e| eval(e, inputs)),
+            Expr::Xor(list) => list.iter().filter(|e| eval(e, inputs)).count() % 2 == 1,
+        }
+    }
+
+    #[test]
+    fn precedence_is_or_xor_and_not() {
+        let (expr, names) = parse_expression("A | B ^ C & !D").unwrap();
+        assert_eq!(names, ["A", "B", "C", "D"]);
+        assert_eq!(
+            expr,
+            Expr::Or(vec![input(0), Expr::Xor(vec![input(1), Expr::And(vec![input(2), not(input(3))])])])
+        );
+    }
+
+    #[test]
+    fn runs_of_one_operator_become_one_node() {
+        let (expr, _) = parse_expression("A & B & C").unwrap();
+        assert_eq!(expr, Expr::And(vec![input(0), input(1), input(2)]));
+    }
+
+    #[test]
+    fn parentheses_and_names_in_alphabetical_order() {
+        let (expr, names) = parse_expression("(carry | b) & a").unwrap();
+        assert_eq!(names, ["a", "b", "carry"]);
+        assert_eq!(expr, Expr::And(vec![Expr::Or(vec![input(2), input(1)]), input(0)]));
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        assert!(parse_expression("A &").is_err());
+        assert!(parse_expression("(A | B").is_err());
+        assert!(parse_expression("A B").is_err());
+        assert!(parse_expression("A $ B").is_err());
+    }
+
+    #[test]
+    fn truth_table_matches_its_rows() {
+        let (expr, names) = parse_truth_table("00 0; 01 1; 10 1; 11 0").unwrap();
+        assert_eq!(names, ["A", "B"]);
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert_eq!(eval(&expr, &[a, b]), a != b);
+        }
+        assert!(parse_truth_table("00 0; 1 1").is_err());
+        assert!(parse_truth_table("1").is_err());
+    }
+
+    #[test]
+    fn truth_tables_are_told_from_expressions() {
+        assert!(is_truth_table("00 0; 01 1"));
+        assert!(!is_truth_table("A & 1"));
+    }
+
+    #[test]
+    fn simplify_folds_constants() {
+        assert_eq!(simplify(Expr::And(vec![input(0), Expr::Const(false)])), Expr::Const(false));
+        assert_eq!(simplify(Expr::And(vec![input(0), Expr::Const(true)])), input(0));
+        assert_eq!(simplify(Expr::Or(vec![input(0), Expr::Const(true)])), Expr::Const(true));
+        assert_eq!(simplify(Expr::Or(vec![Expr::Const(false), input(1)])), input(1));
+        assert_eq!(simplify(Expr::Xor(vec![input(0), Expr::Const(true)])), not(input(0)));
+        assert_eq!(simplify(not(Expr::Const(true))), Expr::Const(false));
+    }
+
+    #[test]
+    fn simplify_removes_double_negation_and_flattens() {
+        assert_eq!(simplify(not(not(input(0)))), input(0));
+        assert_eq!(
+            simplify(Expr::And(vec![input(0), Expr::And(vec![input(1), input(2)])])),
+            Expr::And(vec![input(0), input(1), input(2)])
+        );
+        assert_eq!(
+            simplify(Expr::Xor(vec![Expr::Xor(vec![input(0), input(1)]), Expr::Const(true), Expr::Const(true)])),
+            Expr::Xor(vec![input(0), input(1)])
+        );
+        assert_eq!(
+            simplify(Expr::Xor(vec![input(0), Expr::Const(true), input(1)])),
+            not(Expr::Xor(vec![input(0), input(1)]))
+        );
+    }
+
+    #[test]
+    fn trees_use_only_two_and_three_input_gates() {
+        for operands in 2..=9 {
+            let list: Vec<Expr> = (0..operands).map(input).collect();
+            let mut netlist = Netlist::default();
+            let root = netlist.build(&Expr::And(list)).unwrap();
+
+            assert!(netlist.gates.iter().all(|gate| matches!(gate.inputs.len(), 2 | 3)));
+            // Every operand is read exactly once, and every gate but the root feeds another
+            let reads: Vec<Source> = netlist.gates.iter().flat_map(|gate| gate.inputs.clone()).collect();
+            for i in 0..operands {
+                assert_eq!(reads.iter().filter(|&&s| s == Source::Input(i)).count(), 1);
+            }
+            assert_eq!(reads.len(), operands + netlist.gates.len() - 1);
+            assert_eq!(root, Source::Gate(netlist.gates.len() - 1));
+        }
+    }
+
+    #[test]
+    fn negated_pairs_fold_into_inverted_gates() {
+        let mut netlist = Netlist::default();
+        netlist.build(&not(Expr::Or(vec![input(0), input(1)]))).unwrap();
+        assert_eq!(netlist.gates.len(), 1);
+        assert_eq!(netlist.gates[0].kind, VoxelType::Or(OrVariants::NorGate));
+        assert!(Netlist::default().build(&Expr::Const(true)).is_err());
+    }
+}
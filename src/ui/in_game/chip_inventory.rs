@@ -0,0 +1,61 @@
+use crate::prelude::*;
+
+const CHIP_BUTTON_WIDTH_PERCENT: f32 = 100.0;
+const CHIP_BUTTON_HEIGHT_PERCENT: f32 = 12.0;
+const CHIP_LIST_HEIGHT_PX: f32 = 360.0;
+const EMPTY_HINT: &str = "No chips yet. Select a region and press K to package it.";
+
+/// Spawns the inventory group of custom chips, opened with Shift+Tab.
+pub fn spawn_chip_inventory(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Chips", GameUI::ChipInventory);
+
+    let list = spawn_ui_node(
+        commands,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(CHIP_LIST_HEIGHT_PX),
+            overflow: Overflow::clip_y(),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        ChipInventoryList,
+    );
+    commands.entity(list).set_parent(root);
+
+    root
+}
+
+/// Rebuilds the chip buttons whenever a chip is saved.
+pub fn update_chip_inventory(
+    mut commands: Commands,
+    library: Res<ChipLibrary>,
+    list_query: Query<(Entity, Ref<ChipInventoryList>)>,
+) {
+    for (list, marker) in list_query.iter() {
+        if !library.is_changed() && !marker.is_added() {
+            continue;
+        }
+        commands.entity(list).despawn_descendants();
+
+        let definitions = library.sorted();
+        if definitions.is_empty() {
+            spawn_widget_text(&mut commands, list, EMPTY_HINT, ());
+        }
+        for definition in definitions {
+            let label = format!(
+                "{} ({} in, {} out)",
+                definition.name,
+                definition.inputs.len(),
+                definition.outputs.len()
+            );
+            let button = spawn_text_button(
+                &mut commands,
+                CHIP_BUTTON_WIDTH_PERCENT,
+                CHIP_BUTTON_HEIGHT_PERCENT,
+                label,
+                MenuAction::SelectChip(definition.id()),
+            );
+            commands.entity(button).set_parent(list);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::prelude::*;
+
+const HINT: &str = "name in1 in2 > out1 out2\nPins are named in the order they were marked\nEnter saves, Esc closes";
+
+/// Spawns the widget naming a chip and its pins before it is saved.
+pub fn spawn_chip_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Package Chip", GameUI::ChipWidget);
+    spawn_widget_text_input(commands, root, GameUI::ChipWidget, ChipNameInput);
+    spawn_widget_text(commands, root, "", ChipStatusText);
+    spawn_widget_text(commands, root, HINT, ());
+    root
+}
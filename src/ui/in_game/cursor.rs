@@ -62,6 +62,17 @@ pub fn update_cursor(
                     text.0.0 = "E".to_string();
                     text.1.0 = Color::LinearRgba(color_alpha);
                 }
+                VoxelType::Component(ComponentVariants::ProximitySensor(_)) => {
+                    text.0.0 = "E".to_string();
+                    text.1.0 = Color::LinearRgba(color_alpha);
+                }
+                VoxelType::Component(ComponentVariants::EdgeDetector(_))
+                | VoxelType::Component(ComponentVariants::Monostable(_))
+                | VoxelType::Component(ComponentVariants::Transmitter(_))
+                | VoxelType::Component(ComponentVariants::Receiver(..)) => {
+                    text.0.0 = "E".to_string();
+                    text.1.0 = Color::LinearRgba(color_alpha);
+                }
                 _ => {
                     text.0.0 = "+".to_string();
                     text.1.0 = Color::BLACK;
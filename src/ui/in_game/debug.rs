@@ -35,8 +35,18 @@ pub fn update_debug_text(
     entity_query: Query<Entity>,
     player: Res<Player>,
     time: Res<Time>,
+    save: Res<SavedWorld>,
+    voxel_map: Res<VoxelMap>,
+    mut signal: Local<(Option<IVec3>, u64, Option<String>)>,
 ) {
-    let info = DebugInfo::gather(&player, &time, entity_query.iter().count());
+    // Naming a cable floods its net, so only look again when the crosshair moves
+    // to another cell, the wiring changes or a label is edited
+    let position = player.hit_voxel.map(|voxel| voxel.position);
+    if (position, voxel_map.generation) != (signal.0, signal.1) || save.is_changed() {
+        let name = position.and_then(|position| signal_name(&save.labels, &voxel_map, position));
+        *signal = (position, voxel_map.generation, name.map(str::to_string));
+    }
+    let info = DebugInfo::gather(&player, &time, entity_query.iter().count(), signal.2.as_deref());
     
     for mut text in text_query.iter_mut() {
         text.0 = info.to_string();
@@ -48,14 +58,16 @@ struct DebugInfo<'a> {
     time: &'a Time,
     player: &'a Player,
     entities: usize,
+    signal: Option<&'a str>,
 }
 
 impl<'a> DebugInfo<'a> {
-    fn gather(player: &'a Player, time: &'a Time, entities: usize) -> Self {
+    fn gather(player: &'a Player, time: &'a Time, entities: usize, signal: Option<&'a str>) -> Self {
         Self {
             time,
             player,
             entities,
+            signal,
         }
     }
 }
@@ -75,6 +87,7 @@ impl std::fmt::Display for DebugInfo<'_> {
         writeln!(f)?;
         writeln!(f, "Ray Hit Pos: {:.1}", self.player.ray_hit_pos)?;
         writeln!(f, "Hit Voxel: {:?}", self.player.hit_voxel)?;
+        writeln!(f, "Signal: {}", self.signal.unwrap_or("-"))?;
         writeln!(f, "Ray Distance: {:.1}", self.player.distance)?;
         writeln!(f)?;
         writeln!(f, "Selected Voxel: {:?}", self.player.selected_voxel)?;
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+const EDGE_BUTTON_WIDTH_PERCENT: f32 = 100.0;
+const EDGE_BUTTON_HEIGHT_PERCENT: f32 = 18.0;
+
+/// Spawns the widget used to pick which input edges an edge detector reacts to.
+pub fn spawn_edge_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Edge Detector", GameUI::EdgeWidget);
+
+    let edge_options = [
+        (EdgeMode::Rising, "Rising", Color::linear_rgba(0.24, 0.65, 0.38, 0.95)),
+        (EdgeMode::Falling, "Falling", Color::linear_rgba(0.75, 0.35, 0.23, 0.95)),
+        (EdgeMode::Both, "Both", Color::linear_rgba(0.23, 0.46, 0.75, 0.95)),
+    ];
+
+    for (mode, label, color) in edge_options {
+        let button = spawn_text_button(
+            commands,
+            EDGE_BUTTON_WIDTH_PERCENT,
+            EDGE_BUTTON_HEIGHT_PERCENT,
+            label.to_string(),
+            MenuAction::EdgeSetting(mode),
+        );
+        commands.entity(button).set_parent(root);
+        commands
+            .entity(button)
+            .insert((BackgroundColor(color), BorderRadius::all(Val::Px(10.0))));
+    }
+
+    root
+}
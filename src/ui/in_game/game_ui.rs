@@ -53,6 +53,16 @@ pub fn setup_ui(
         spawn_debug_text(&mut commands),
         spawn_speed_indicator(&mut commands, speed_indicator_texture, speed_indicator_atlas_handle),
         spawn_clock_widget(&mut commands),
+        spawn_sensor_widget(&mut commands),
+        spawn_edge_widget(&mut commands),
+        spawn_monostable_widget(&mut commands),
+        spawn_wireless_widget(&mut commands),
+        spawn_tool_readout(&mut commands),
+        spawn_synth_widget(&mut commands),
+        spawn_chip_widget(&mut commands),
+        spawn_chip_inventory(&mut commands),
+        spawn_label_widget(&mut commands),
+        spawn_hover_panel(&mut commands),
     ];
 
     for child in children {
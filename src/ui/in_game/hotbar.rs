@@ -167,7 +167,7 @@ pub fn update_hotbar(
         let kind = player.hotbar[slot.index];
 
         if let Some(atlas) = &mut img_node.texture_atlas {
-            if let Some(asset) = voxel_map.asset_map.get(&kind) {
+            if let Some(asset) = voxel_map.asset(kind) {
                 atlas.index = asset.texture_row;      // ← same data, new key
             }
         }
@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use crate::prelude::*;
+
+const PANEL_TOP_PERCENT: f32 = 30.0;
+const PANEL_RIGHT_PERCENT: f32 = 2.0;
+const PANEL_FONT_SIZE: f32 = 16.0;
+
+/// Spawns the panel describing the voxel under the crosshair.
+pub fn spawn_hover_panel(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(PANEL_TOP_PERCENT),
+                right: Val::Percent(PANEL_RIGHT_PERCENT),
+                padding: UiRect::all(Val::Px(10.0)),
+                display: Display::None,
+                ..default()
+            },
+            Text::new(""),
+            TextFont {
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::srgb(0.92, 0.95, 1.0)),
+            TextLayout::new_with_justify(JustifyText::Left),
+            BackgroundColor(Color::linear_rgba(0.08, 0.09, 0.13, 0.8)),
+            BorderRadius::all(Val::Px(8.0)),
+            HoverPanelText,
+            GameUI::Default,
+        ))
+        .id()
+}
+
+/// Shows the kind, position, facing, state and port connections of `Player::hit_voxel`.
+pub fn update_hover_panel(
+    mut query: Query<(&mut Text, &mut Node), With<HoverPanelText>>,
+    player: Res<Player>,
+    voxel_map: Res<VoxelMap>,
+    chip_library: Res<ChipLibrary>,
+) {
+    let description = player
+        .hit_voxel
+        .map(|voxel| describe_voxel(&voxel, &voxel_map, &chip_library));
+
+    for (mut text, mut node) in query.iter_mut() {
+        let Some(description) = &description else {
+            node.display = Display::None;
+            continue;
+        };
+        node.display = Display::Flex;
+        if text.0 != *description {
+            text.0 = description.clone();
+        }
+    }
+}
+
+fn describe_voxel(voxel: &Voxel, voxel_map: &VoxelMap, chip_library: &ChipLibrary) -> String {
+    let name = match voxel.kind {
+        VoxelType::Chip(kind) => chip_library.name(kind.id).map(str::to_string),
+        kind => voxel_map.asset(kind).map(|asset| asset.definition.name.clone()),
+    }
+    .unwrap_or_else(|| format!("{:?}", voxel.kind));
+
+    let mut text = String::new();
+    let p = voxel.position;
+    let _ = writeln!(text, "{name}");
+    let _ = writeln!(text, "Position: {}, {}, {}", p.x, p.y, p.z);
+    let _ = writeln!(text, "Facing: {}", offset_name(voxel_facing(voxel)));
+
+    let (inputs, outputs) = voxel_directions(voxel);
+    let _ = writeln!(text, "State: {}", word_text(voxel.state));
+    for port in 1..outputs.len() {
+        let _ = writeln!(text, "Port {port}: {}", word_text(voxel.port_state(port)));
+    }
+
+    if let VoxelType::Component(ComponentVariants::Clock(period)) = voxel.kind {
+        let clock = if period == 0 { "stopped".to_string() } else { format!("every {period} ticks") };
+        let _ = writeln!(text, "Clock: {clock}");
+    }
+
+    let _ = writeln!(text, "Inputs: {}", ports_text(voxel, &inputs, voxel_map));
+    let _ = write!(text, "Outputs: {}", ports_text(voxel, &outputs, voxel_map));
+    text
+}
+
+/// The word in binary, grouped by nibble, and in hex.
+fn word_text(word: Bits16) -> String {
+    let bits = format!("{:016b}", word.value());
+    let nibbles: Vec<&str> = (0..4).map(|i| &bits[i * 4..i * 4 + 4]).collect();
+    format!("{} 0x{:04X}", nibbles.join(" "), word.value())
+}
+
+fn offset_name(dir: IVec3) -> &'static str {
+    match (dir.x, dir.y, dir.z) {
+        (1, 0, 0) => "+X",
+        (-1, 0, 0) => "-X",
+        (0, 1, 0) => "+Y",
+        (0, -1, 0) => "-Y",
+        (0, 0, 1) => "+Z",
+        (0, 0, -1) => "-Z",
+        _ => "?",
+    }
+}
+
+/// Each port by side, marked connected when a voxel sits there that doesn't
+/// insulate the face towards this one. A port on the voxel itself shows as `self`.
+fn ports_text(voxel: &Voxel, ports: &[IVec3], voxel_map: &VoxelMap) -> String {
+    if ports.is_empty() {
+        return "none".to_string();
+    }
+    ports
+        .iter()
+        .map(|port| {
+            // Lights, transmitters, pistons and doors read their own state
+            if *port == voxel.position {
+                return "self".to_string();
+            }
+            let connected = voxel_map
+                .voxel_map
+                .get(port)
+                .is_some_and(|neighbor| !neighbor.is_insulated(voxel.position - *port));
+            let state = if connected { "connected" } else { "open" };
+            format!("{} {state}", offset_name(*port - voxel.position))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -33,10 +33,13 @@ pub fn spawn_identifier(commands: &mut Commands) -> Entity {
 }
 
 
-/// Updates the voxel identifier text with a fade effect.
+/// Updates the voxel identifier text with a fade effect. While a net is
+/// highlighted it shows the net's size and fan-in / fan-out instead.
 pub fn update_identifier(
     mut query: Query<(&mut Text, &mut TextColor), With<VoxelIdentifierText>>,
     player: Res<Player>,
+    chip_library: Res<ChipLibrary>,
+    highlight: Res<NetHighlight>,
     time: Res<Time>,
     mut previous_selected: Local<usize>,
     mut fade_timer: Local<Option<Timer>>,
@@ -53,13 +56,25 @@ pub fn update_identifier(
     }
     *previous_selected = player.hotbar_selector;
 
-    let alpha = timer.fraction_remaining() + 0.25;
+    let alpha = if highlight.is_empty() { timer.fraction_remaining() + 0.25 } else { 1.0 };
         
     let new_color = Color::linear_rgba(0.85, 0.85, 0.85, alpha);
 
+    // Custom chips share one descriptor, so name them after their definition.
+    let name = match player.hotbar[player.hotbar_selector] {
+        _ if !highlight.is_empty() => format!(
+            "Net: {} cells, fan-in {}, fan-out {}",
+            highlight.cells.len(),
+            highlight.drivers.len(),
+            highlight.readers.len()
+        ),
+        VoxelType::Chip(kind) => chip_library.name(kind.id).unwrap_or(&descriptor.name).to_string(),
+        _ => descriptor.name.clone(),
+    };
+
     // Update text and color for all identifier components.
     for (mut text, mut text_color) in query.iter_mut() {
-        text.0 = descriptor.name.clone();
+        text.0 = name.clone();
         text_color.0 = new_color;
     }
 }
@@ -0,0 +1,11 @@
+use crate::prelude::*;
+
+const HINT: &str = "Enter attaches the label, an empty label removes it\nShift+B shows a voxel's labels from every side\nEsc closes";
+
+/// Spawns the widget editing the label on a voxel face.
+pub fn spawn_label_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Label", GameUI::LabelWidget);
+    spawn_widget_text_input(commands, root, GameUI::LabelWidget, LabelInput);
+    spawn_widget_text(commands, root, HINT, ());
+    root
+}
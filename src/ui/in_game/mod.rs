@@ -6,4 +6,15 @@ pub mod identifier;
 pub mod debug;
 pub mod exit_menu;
 pub mod speed_indicator;
-pub mod clock_widget;
\ No newline at end of file
+pub mod clock_widget;
+pub mod sensor_widget;
+pub mod edge_widget;
+pub mod monostable_widget;
+pub mod wireless_widget;
+pub mod tool_readout;
+pub mod synth_widget;
+pub mod chip_widget;
+pub mod chip_inventory;
+pub mod label_widget;
+pub mod world_labels;
+pub mod hover_panel;
\ No newline at end of file
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+const WIDTH_BUTTON_WIDTH_PERCENT: f32 = 100.0;
+const WIDTH_BUTTON_HEIGHT_PERCENT: f32 = 12.0;
+
+/// Spawns the widget used to pick the pulse width of a monostable.
+pub fn spawn_monostable_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Pulse Width", GameUI::MonostableWidget);
+
+    let width_colors = [
+        Color::linear_rgba(0.75, 0.23, 0.23, 0.95),
+        Color::linear_rgba(0.75, 0.42, 0.23, 0.95),
+        Color::linear_rgba(0.72, 0.6, 0.24, 0.95),
+        Color::linear_rgba(0.5, 0.65, 0.26, 0.95),
+        Color::linear_rgba(0.24, 0.65, 0.38, 0.95),
+    ];
+
+    for (width, color) in PULSE_WIDTHS.into_iter().zip(width_colors) {
+        let label = if width == 1 { "1 tick".to_string() } else { format!("{width} ticks") };
+        let button = spawn_text_button(
+            commands,
+            WIDTH_BUTTON_WIDTH_PERCENT,
+            WIDTH_BUTTON_HEIGHT_PERCENT,
+            label,
+            MenuAction::PulseWidth(width),
+        );
+        commands.entity(button).set_parent(root);
+        commands
+            .entity(button)
+            .insert((BackgroundColor(color), BorderRadius::all(Val::Px(10.0))));
+    }
+
+    root
+}
@@ -0,0 +1,32 @@
+use crate::prelude::*;
+
+const RADIUS_BUTTON_WIDTH_PERCENT: f32 = 100.0;
+const RADIUS_BUTTON_HEIGHT_PERCENT: f32 = 14.0;
+
+/// Spawns the widget used to pick the detection radius of a proximity sensor.
+pub fn spawn_sensor_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Sensor Radius", GameUI::SensorWidget);
+
+    let radius_colors = [
+        Color::linear_rgba(0.23, 0.46, 0.75, 0.95),
+        Color::linear_rgba(0.24, 0.55, 0.68, 0.95),
+        Color::linear_rgba(0.24, 0.6, 0.52, 0.95),
+        Color::linear_rgba(0.24, 0.65, 0.38, 0.95),
+    ];
+
+    for (radius, color) in PROXIMITY_RADII.into_iter().zip(radius_colors) {
+        let button = spawn_text_button(
+            commands,
+            RADIUS_BUTTON_WIDTH_PERCENT,
+            RADIUS_BUTTON_HEIGHT_PERCENT,
+            format!("{radius} blocks"),
+            MenuAction::SensorRadius(radius),
+        );
+        commands.entity(button).set_parent(root);
+        commands
+            .entity(button)
+            .insert((BackgroundColor(color), BorderRadius::all(Val::Px(10.0))));
+    }
+
+    root
+}
@@ -0,0 +1,12 @@
+use crate::prelude::*;
+
+const HINT: &str = "(A & !B) | C  -  ! & ^ | and ()\n00 0; 01 1; 10 1; 11 0  -  truth table\nEnter builds, Esc closes";
+
+/// Spawns the widget for synthesizing gates from a boolean expression or truth table.
+pub fn spawn_synth_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Synthesize", GameUI::SynthWidget);
+    spawn_widget_text_input(commands, root, GameUI::SynthWidget, SynthInput);
+    spawn_widget_text(commands, root, "", SynthStatusText);
+    spawn_widget_text(commands, root, HINT, ());
+    root
+}
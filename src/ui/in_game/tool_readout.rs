@@ -0,0 +1,89 @@
+use crate::prelude::*;
+
+const READOUT_TOP_PERCENT: f32 = 55.0;
+const READOUT_LEFT_PERCENT: f32 = 53.0;
+const READOUT_FONT_SIZE: f32 = 20.0;
+
+/// Spawns the text next to the crosshair describing the active build tool.
+pub fn spawn_tool_readout(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(READOUT_TOP_PERCENT),
+                left: Val::Percent(READOUT_LEFT_PERCENT),
+                ..default()
+            },
+            Text::new(""),
+            TextFont {
+                font_size: READOUT_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            TextLayout::new_with_justify(JustifyText::Left),
+            ToolReadoutText,
+            GameUI::Default,
+        ))
+        .id()
+}
+
+/// Shows the settings of the active build tool, e.g. the length of a line being
+/// drawn, followed by the latest `ToolMessage`.
+pub fn update_tool_readout(
+    mut query: Query<&mut Text, With<ToolReadoutText>>,
+    tool: Res<ActiveTool>,
+    clipboard: Res<Clipboard>,
+    array: Res<ArraySettings>,
+    line: Res<LineDrag>,
+    router: Res<Router>,
+    draft: Res<ChipDraft>,
+    selection: Res<Selection>,
+    time: Res<Time>,
+    mut message: ResMut<ToolMessage>,
+) {
+    let mut readout = match *tool {
+        ActiveTool::None => String::new(),
+        ActiveTool::Paste => {
+            let name = clipboard.blueprint.as_ref().map_or("", |blueprint| blueprint.name.as_str());
+            let mirrored = if clipboard.mirrored { ", mirrored" } else { "" };
+            format!("Paste {name} ({}°{mirrored})", clipboard.turns as u32 * 90)
+        }
+        ActiveTool::Array => {
+            let channels = if array.increment_channels { ", channel +1" } else { "" };
+            format!("Array x{}, stride {}{channels}", array.count, array.stride)
+        }
+        ActiveTool::Line => match line.path().len() {
+            0 => "Line".to_string(),
+            length => format!("Line {length}"),
+        },
+        ActiveTool::Route => {
+            let layer = router.layer.map_or("any".to_string(), |layer| layer.to_string());
+            let pick = if router.source.is_some() { "input" } else { "output" };
+            format!("Route channel {}, layer {layer}: pick {pick}", router.channel)
+        }
+        ActiveTool::Chip => format!(
+            "Chip: {} in, {} out: K names it",
+            draft.inputs.len(),
+            draft.outputs.len()
+        ),
+    };
+
+    // Why region operations refuse an oversized box, then the latest tool feedback
+    message.timer.tick(time.delta());
+    let notes = [
+        selection.check_volume().err(),
+        (!message.timer.finished()).then(|| message.text.clone()),
+    ];
+    for note in notes.into_iter().flatten() {
+        if !readout.is_empty() {
+            readout.push('\n');
+        }
+        readout.push_str(&note);
+    }
+
+    for mut text in query.iter_mut() {
+        if text.0 != readout {
+            text.0 = readout.clone();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::prelude::*;
+
+const FREQUENCY_BUTTON_WIDTH_PERCENT: f32 = 20.0;
+const FREQUENCY_BUTTON_HEIGHT_PERCENT: f32 = 100.0;
+const DELAY_BUTTON_WIDTH_PERCENT: f32 = 100.0;
+const DELAY_BUTTON_HEIGHT_PERCENT: f32 = 14.0;
+
+/// Spawns the widget used to tune transmitters and receivers.
+///
+/// The delay buttons only affect receivers.
+pub fn spawn_wireless_widget(commands: &mut Commands) -> Entity {
+    let root = spawn_widget_panel(commands, "Frequency", GameUI::WirelessWidget);
+
+    let frequency_grid = spawn_ui_node(
+        commands,
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        (),
+    );
+    commands.entity(frequency_grid).set_parent(root);
+
+    for frequency in 0..WIRELESS_FREQUENCIES {
+        let button = spawn_text_button(
+            commands,
+            FREQUENCY_BUTTON_WIDTH_PERCENT,
+            FREQUENCY_BUTTON_HEIGHT_PERCENT,
+            frequency.to_string(),
+            MenuAction::Frequency(frequency),
+        );
+        commands.entity(button).set_parent(frequency_grid);
+        commands.entity(button).insert((
+            BackgroundColor(Color::linear_rgba(0.23, 0.46, 0.75, 0.95)),
+            BorderRadius::all(Val::Px(10.0)),
+        ));
+    }
+
+    let delay_options = [
+        (false, "Instant", Color::linear_rgba(0.24, 0.65, 0.38, 0.95)),
+        (true, "1 tick delay", Color::linear_rgba(0.72, 0.6, 0.24, 0.95)),
+    ];
+
+    for (delayed, label, color) in delay_options {
+        let button = spawn_text_button(
+            commands,
+            DELAY_BUTTON_WIDTH_PERCENT,
+            DELAY_BUTTON_HEIGHT_PERCENT,
+            label.to_string(),
+            MenuAction::ReceiverDelay(delayed),
+        );
+        commands.entity(button).set_parent(root);
+        commands
+            .entity(button)
+            .insert((BackgroundColor(color), BorderRadius::all(Val::Px(10.0))));
+    }
+
+    root
+}
@@ -0,0 +1,83 @@
+use crate::prelude::*;
+
+const LABEL_FONT_SIZE: f32 = 22.0;
+/// Distance at which a label is drawn at `LABEL_FONT_SIZE`; it shrinks further away.
+const LABEL_REFERENCE_DISTANCE: f32 = 4.0;
+const LABEL_VIEW_DISTANCE: f32 = 24.0;
+/// How far in front of its face a label floats, so it never sinks into the voxel.
+const LABEL_FACE_OFFSET: f32 = 0.55;
+
+fn spawn_label_text(commands: &mut Commands, index: usize) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::axes(Val::Px(4.0), Val::Px(1.0)),
+            display: Display::None,
+            ..default()
+        },
+        Text::new(""),
+        TextFont {
+            font_size: LABEL_FONT_SIZE,
+            ..default()
+        },
+        TextColor(Color::srgb(0.95, 0.95, 0.85)),
+        BackgroundColor(Color::linear_rgba(0.02, 0.02, 0.04, 0.6)),
+        BorderRadius::all(Val::Px(4.0)),
+        WorldLabelText(index),
+        GameEntity,
+        GameUI::Default,
+    ));
+}
+
+/// Draws the world labels as screen text over the face they are attached to.
+/// Labels only show from in front of their face unless they are always visible.
+pub fn update_world_labels(
+    mut commands: Commands,
+    save: Res<SavedWorld>,
+    player: Res<Player>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    mut text_query: Query<(Entity, &WorldLabelText, &mut Node, &mut Text, &mut TextFont, &ComputedNode)>,
+) {
+    // One text per label, matched by index
+    let mut spawned = vec![false; save.labels.len()];
+    for (entity, label_text, ..) in text_query.iter() {
+        match spawned.get_mut(label_text.0) {
+            Some(found) => *found = true,
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+    for (index, _) in spawned.iter().enumerate().filter(|(_, found)| !**found) {
+        spawn_label_text(&mut commands, index);
+    }
+
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    for (_, label_text, mut node, mut text, mut font, computed) in text_query.iter_mut() {
+        let Some(label) = save.labels.get(label_text.0) else {
+            continue;
+        };
+        if text.0 != label.text {
+            text.0 = label.text.clone();
+        }
+
+        let anchor = label.position.as_vec3() + label.face.as_vec3() * LABEL_FACE_OFFSET;
+        let to_camera = player.camera_pos - anchor;
+        let distance = to_camera.length();
+        let facing = label.always_visible || to_camera.dot(label.face.as_vec3()) > 0.0;
+        let screen = camera.world_to_viewport(camera_transform, anchor).ok();
+        let (Some(screen), true, true) = (screen, facing, distance <= LABEL_VIEW_DISTANCE) else {
+            node.display = Display::None;
+            continue;
+        };
+
+        let size = computed.size() * computed.inverse_scale_factor();
+        node.display = Display::Flex;
+        node.left = Val::Px(screen.x - size.x / 2.0);
+        node.top = Val::Px(screen.y - size.y / 2.0);
+        let font_size = LABEL_FONT_SIZE * (LABEL_REFERENCE_DISTANCE / distance.max(1.0)).clamp(0.5, 1.5);
+        if font.font_size != font_size {
+            font.font_size = font_size;
+        }
+    }
+}
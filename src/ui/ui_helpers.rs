@@ -1,8 +1,12 @@
 use crate::prelude::*;
 use bevy_simple_text_input::{
-    TextInput, TextInputSettings, TextInputSubmitEvent, TextInputTextColor, TextInputTextFont,
+    TextInput, TextInputInactive, TextInputSettings, TextInputSubmitEvent, TextInputTextColor,
+    TextInputTextFont,
 };
 
+const WIDGET_INPUT_FONT_SIZE: f32 = 24.0;
+const WIDGET_TEXT_FONT_SIZE: f32 = 16.0;
+
 /// A generic helper that spawns a UI node with a given style and additional components.
 pub fn spawn_ui_node<B: Bundle>(commands: &mut Commands, style: Node, bundle: B) -> Entity {
     commands.spawn((style, bundle)).id()
@@ -61,6 +65,72 @@ pub fn spawn_popup(
     )
 }
 
+const WIDGET_PANEL_WIDTH_PERCENT: f32 = 24.0;
+const WIDGET_PANEL_MIN_WIDTH_PX: f32 = 240.0;
+const WIDGET_PANEL_MAX_WIDTH_PX: f32 = 320.0;
+const WIDGET_PANEL_TOP_PERCENT: f32 = 10.0;
+const WIDGET_PANEL_RIGHT_PERCENT: f32 = 38.0;
+const WIDGET_PANEL_PADDING_PX: f32 = 18.0;
+const WIDGET_PANEL_BORDER_PX: f32 = 2.0;
+const WIDGET_PANEL_GAP_PX: f32 = 14.0;
+
+/// Spawns the panel and header shared by the voxel interaction widgets (clock, sensor, ...).
+/// Children such as setting buttons are attached to the returned entity.
+pub fn spawn_widget_panel(commands: &mut Commands, title: &str, widget: GameUI) -> Entity {
+    let root = spawn_ui_node(
+        commands,
+        Node {
+            width: Val::Percent(WIDGET_PANEL_WIDTH_PERCENT),
+            min_width: Val::Px(WIDGET_PANEL_MIN_WIDTH_PX),
+            max_width: Val::Px(WIDGET_PANEL_MAX_WIDTH_PX),
+            top: Val::Percent(WIDGET_PANEL_TOP_PERCENT),
+            right: Val::Percent(WIDGET_PANEL_RIGHT_PERCENT),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Stretch,
+            justify_content: JustifyContent::FlexStart,
+            padding: UiRect::all(Val::Px(WIDGET_PANEL_PADDING_PX)),
+            border: UiRect::all(Val::Px(WIDGET_PANEL_BORDER_PX)),
+            row_gap: Val::Px(WIDGET_PANEL_GAP_PX),
+            ..default()
+        },
+        (
+            BackgroundColor(Color::linear_rgba(0.08, 0.09, 0.13, 0.92)),
+            BorderColor(Color::srgb(0.35, 0.55, 0.9)),
+            BorderRadius::all(Val::Px(12.0)),
+            BoxShadow {
+                color: Color::BLACK.with_alpha(0.45),
+                x_offset: Val::Px(0.0),
+                y_offset: Val::Px(8.0),
+                spread_radius: Val::Px(0.0),
+                blur_radius: Val::Px(20.0),
+            },
+            widget,
+        ),
+    );
+    
+    let header = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Text::new(title),
+            TextFont {
+                font_size: 30.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.92, 0.95, 1.0)),
+            TextLayout::new_with_justify(JustifyText::Center),
+        ))
+        .id();
+
+    commands.entity(header).set_parent(root);
+    
+    root
+}
+
 /// Creates a sub-node that can be attached as a child (for instance, to hold extra buttons).
 pub fn spawn_sub_node(commands: &mut Commands, width: f32, height: f32, bottom: f32) -> Entity {
     spawn_ui_node(
@@ -113,6 +183,74 @@ pub fn create_editable_text(commands: &mut Commands) -> Entity {
     edit_text
 }
 
+/// Adds a one-line text input to the widget panel `root` of `widget`. It only
+/// takes the keyboard while that widget is open, see `focus_widget_inputs`.
+pub fn spawn_widget_text_input<B: Bundle>(
+    commands: &mut Commands,
+    root: Entity,
+    widget: GameUI,
+    marker: B,
+) -> Entity {
+    let input = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                border: UiRect::all(Val::Px(3.0)),
+                padding: UiRect::all(Val::Px(5.0)),
+                ..default()
+            },
+            BorderColor(Color::srgb(0.75, 0.52, 0.99)),
+            BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+            TextInput,
+            TextInputTextFont(TextFont {
+                font_size: WIDGET_INPUT_FONT_SIZE,
+                ..default()
+            }),
+            TextInputTextColor(TextColor(Color::srgb(0.9, 0.9, 0.9))),
+            TextInputSettings {
+                retain_on_submit: true,
+                ..default()
+            },
+            TextInputInactive(true),
+            WidgetInput(widget),
+            marker,
+        ))
+        .id();
+    commands.entity(input).set_parent(root);
+    input
+}
+
+/// Adds a line of small text to the widget panel `root`.
+pub fn spawn_widget_text<B: Bundle>(commands: &mut Commands, root: Entity, text: &str, marker: B) -> Entity {
+    let line = commands
+        .spawn((
+            Text::new(text),
+            TextFont {
+                font_size: WIDGET_TEXT_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::srgb(0.92, 0.95, 1.0)),
+            marker,
+        ))
+        .id();
+    commands.entity(line).set_parent(root);
+    line
+}
+
+/// Focuses each widget text input only while its widget is open, so typing
+/// elsewhere never ends up in it.
+pub fn focus_widget_inputs(
+    current_ui: Res<GameUI>,
+    mut query: Query<(&WidgetInput, &mut TextInputInactive)>,
+) {
+    if !current_ui.is_changed() {
+        return;
+    }
+    for (input, mut inactive) in query.iter_mut() {
+        inactive.0 = *current_ui != input.0;
+    }
+}
+
 pub fn edit_text_listener(
     mut events: EventReader<TextInputSubmitEvent>,
     //mut save_world: ResMut<SavedWorld>,
@@ -125,6 +263,7 @@ pub fn edit_text_listener(
         let world = SavedWorld {
             world_name: sanitary_name,
             voxels: Vec::new(),
+            labels: Vec::new(),
         };
         event_writer.send(GameEvent::SaveWorld { world: (world) });
     }
@@ -150,6 +150,144 @@ pub fn create_voxel_mesh(tile_row: usize) -> Mesh {
 }
 
 
+// Helper to compute UVs for a face.
+fn compute_face_uvs(
+    tile_index: usize,
+    v_top: f32,
+    v_bottom: f32,
+    order: [usize; 4],
+    num_tiles_x: f32,
+) -> [[f32; 2]; 4] {
+    let u_min = tile_index as f32 / num_tiles_x;
+    let u_max = (tile_index as f32 + 1.0) / num_tiles_x;
+    let default_uvs = [
+        [u_min, v_bottom],
+        [u_min, v_top],
+        [u_max, v_top],
+        [u_max, v_bottom],
+    ];
+    [
+        default_uvs[order[0]],
+        default_uvs[order[1]],
+        default_uvs[order[2]],
+        default_uvs[order[3]],
+    ]
+}
+
+// Helper to add a cuboid with per-face UVs based on tile indices.
+fn add_cuboid(
+    positions: &mut Vec<[f32; 3]>,
+    uvs: &mut Vec<[f32; 2]>,
+    normals: &mut Vec<[f32; 3]>,
+    indices: &mut Vec<u32>,
+    index_offset: &mut u32,
+    dims: [f32; 3],
+    offset: [f32; 3],
+    v_top: f32,
+    v_bottom: f32,
+    num_tiles_x: f32,
+) {
+    let hx = dims[0] * 0.5;
+    let hy = dims[1] * 0.5;
+    let hz = dims[2] * 0.5;
+
+    // Closure to push a face.
+    let mut push_face = |face_positions: &[[f32; 3]; 4],
+                           tile_index: usize,
+                           uv_order: [usize; 4],
+                           normal: [f32; 3]| {
+        let start = *index_offset;
+        positions.extend_from_slice(face_positions);
+        let face_uvs = compute_face_uvs(tile_index, v_top, v_bottom, uv_order, num_tiles_x);
+        uvs.extend_from_slice(&face_uvs);
+        for _ in 0..4 {
+            normals.push(normal);
+        }
+        indices.extend_from_slice(&[start, start + 1, start + 2, start, start + 2, start + 3]);
+        *index_offset += 4;
+    };
+
+    // For each face, we now assign a tile index (0–5) so that the U coordinates are a subrange
+    // of [0,1] rather than the full range. You can adjust these indices and UV orders as needed.
+    push_face(
+        // Top face (+Y)
+        &[
+            [offset[0] - hx, offset[1] + hy, offset[2] - hz],
+            [offset[0] - hx, offset[1] + hy, offset[2] + hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] + hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] - hz],
+        ],
+        0, // tile index for top face
+        [0, 1, 2, 3],
+        [0.0, 1.0, 0.0],
+    );
+
+    push_face(
+        // Bottom face (-Y)
+        &[
+            [offset[0] - hx, offset[1] - hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] - hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] - hy, offset[2] + hz],
+            [offset[0] - hx, offset[1] - hy, offset[2] + hz],
+        ],
+        1, // tile index for bottom face
+        [0, 3, 2, 1],
+        [0.0, -1.0, 0.0],
+    );
+
+    push_face(
+        // Right face (+X)
+        &[
+            [offset[0] + hx, offset[1] - hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] + hz],
+            [offset[0] + hx, offset[1] - hy, offset[2] + hz],
+        ],
+        2, // tile index for right face
+        [0, 1, 2, 3],
+        [1.0, 0.0, 0.0],
+    );
+
+    push_face(
+        // Left face (-X)
+        &[
+            [offset[0] - hx, offset[1] - hy, offset[2] + hz],
+            [offset[0] - hx, offset[1] + hy, offset[2] + hz],
+            [offset[0] - hx, offset[1] + hy, offset[2] - hz],
+            [offset[0] - hx, offset[1] - hy, offset[2] - hz],
+        ],
+        3, // tile index for left face
+        [3, 0, 1, 2],
+        [-1.0, 0.0, 0.0],
+    );
+
+    push_face(
+        // Front face (-Z)
+        &[
+            [offset[0] - hx, offset[1] - hy, offset[2] - hz],
+            [offset[0] - hx, offset[1] + hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] - hz],
+            [offset[0] + hx, offset[1] - hy, offset[2] - hz],
+        ],
+        4, // tile index for front face
+        [0, 1, 2, 3],
+        [0.0, 0.0, -1.0],
+    );
+
+    push_face(
+        // Back face (+Z)
+        &[
+            [offset[0] - hx, offset[1] - hy, offset[2] + hz],
+            [offset[0] + hx, offset[1] - hy, offset[2] + hz],
+            [offset[0] + hx, offset[1] + hy, offset[2] + hz],
+            [offset[0] - hx, offset[1] + hy, offset[2] + hz],
+        ],
+        5, // tile index for back face
+        [0, 3, 2, 1],
+        [0.0, 0.0, 1.0],
+    );
+}
+
 pub fn create_cable_mesh(tile_row: usize, connections: [bool; 6]) -> Mesh {
     // Texture atlas setup.
     let num_tiles_x = 6.0;
@@ -163,143 +301,6 @@ pub fn create_cable_mesh(tile_row: usize, connections: [bool; 6]) -> Mesh {
     let mut indices = Vec::new();
     let mut index_offset = 0u32;
 
-    // Helper to compute UVs for a face.
-    fn compute_face_uvs(
-        tile_index: usize,
-        v_top: f32,
-        v_bottom: f32,
-        order: [usize; 4],
-        num_tiles_x: f32,
-    ) -> [[f32; 2]; 4] {
-        let u_min = tile_index as f32 / num_tiles_x;
-        let u_max = (tile_index as f32 + 1.0) / num_tiles_x;
-        let default_uvs = [
-            [u_min, v_bottom],
-            [u_min, v_top],
-            [u_max, v_top],
-            [u_max, v_bottom],
-        ];
-        [
-            default_uvs[order[0]],
-            default_uvs[order[1]],
-            default_uvs[order[2]],
-            default_uvs[order[3]],
-        ]
-    }
-
-    // Helper to add a cuboid with per-face UVs based on tile indices.
-    fn add_cuboid(
-        positions: &mut Vec<[f32; 3]>,
-        uvs: &mut Vec<[f32; 2]>,
-        normals: &mut Vec<[f32; 3]>,
-        indices: &mut Vec<u32>,
-        index_offset: &mut u32,
-        dims: [f32; 3],
-        offset: [f32; 3],
-        v_top: f32,
-        v_bottom: f32,
-        num_tiles_x: f32,
-    ) {
-        let hx = dims[0] * 0.5;
-        let hy = dims[1] * 0.5;
-        let hz = dims[2] * 0.5;
-
-        // Closure to push a face.
-        let mut push_face = |face_positions: &[[f32; 3]; 4],
-                               tile_index: usize,
-                               uv_order: [usize; 4],
-                               normal: [f32; 3]| {
-            let start = *index_offset;
-            positions.extend_from_slice(face_positions);
-            let face_uvs = compute_face_uvs(tile_index, v_top, v_bottom, uv_order, num_tiles_x);
-            uvs.extend_from_slice(&face_uvs);
-            for _ in 0..4 {
-                normals.push(normal);
-            }
-            indices.extend_from_slice(&[start, start + 1, start + 2, start, start + 2, start + 3]);
-            *index_offset += 4;
-        };
-
-        // For each face, we now assign a tile index (0–5) so that the U coordinates are a subrange
-        // of [0,1] rather than the full range. You can adjust these indices and UV orders as needed.
-        push_face(
-            // Top face (+Y)
-            &[
-                [offset[0] - hx, offset[1] + hy, offset[2] - hz],
-                [offset[0] - hx, offset[1] + hy, offset[2] + hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] + hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] - hz],
-            ],
-            0, // tile index for top face
-            [0, 1, 2, 3],
-            [0.0, 1.0, 0.0],
-        );
-
-        push_face(
-            // Bottom face (-Y)
-            &[
-                [offset[0] - hx, offset[1] - hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] - hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] - hy, offset[2] + hz],
-                [offset[0] - hx, offset[1] - hy, offset[2] + hz],
-            ],
-            1, // tile index for bottom face
-            [0, 3, 2, 1],
-            [0.0, -1.0, 0.0],
-        );
-
-        push_face(
-            // Right face (+X)
-            &[
-                [offset[0] + hx, offset[1] - hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] + hz],
-                [offset[0] + hx, offset[1] - hy, offset[2] + hz],
-            ],
-            2, // tile index for right face
-            [0, 1, 2, 3],
-            [1.0, 0.0, 0.0],
-        );
-
-        push_face(
-            // Left face (-X)
-            &[
-                [offset[0] - hx, offset[1] - hy, offset[2] + hz],
-                [offset[0] - hx, offset[1] + hy, offset[2] + hz],
-                [offset[0] - hx, offset[1] + hy, offset[2] - hz],
-                [offset[0] - hx, offset[1] - hy, offset[2] - hz],
-            ],
-            3, // tile index for left face
-            [3, 0, 1, 2],
-            [-1.0, 0.0, 0.0],
-        );
-
-        push_face(
-            // Front face (-Z)
-            &[
-                [offset[0] - hx, offset[1] - hy, offset[2] - hz],
-                [offset[0] - hx, offset[1] + hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] - hz],
-                [offset[0] + hx, offset[1] - hy, offset[2] - hz],
-            ],
-            4, // tile index for front face
-            [0, 1, 2, 3],
-            [0.0, 0.0, -1.0],
-        );
-
-        push_face(
-            // Back face (+Z)
-            &[
-                [offset[0] - hx, offset[1] - hy, offset[2] + hz],
-                [offset[0] + hx, offset[1] - hy, offset[2] + hz],
-                [offset[0] + hx, offset[1] + hy, offset[2] + hz],
-                [offset[0] - hx, offset[1] + hy, offset[2] + hz],
-            ],
-            5, // tile index for back face
-            [0, 3, 2, 1],
-            [0.0, 0.0, 1.0],
-        );
-    }
     let mut bool_count = 0;
     
     let mut core_dims = [0.3, 0.3, 0.3];
@@ -442,4 +443,66 @@ pub fn create_cable_mesh(tile_row: usize, connections: [bool; 6]) -> Mesh {
     .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
     .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
     .with_inserted_indices(Indices::U32(indices))
-}
\ No newline at end of file
+}
+/// Builds a mesh out of axis-aligned cuboids `(dims, offset)` that share one texture row.
+fn create_cuboid_mesh(tile_row: usize, cuboids: &[([f32; 3], [f32; 3])]) -> Mesh {
+    let num_tiles_x = 6.0;
+    let num_rows_f = NUM_VOXELS as f32;
+    let v_top = tile_row as f32 / num_rows_f;
+    let v_bottom = (tile_row as f32 + 1.0) / num_rows_f;
+
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut normals = Vec::new();
+    let mut indices = Vec::new();
+    let mut index_offset = 0u32;
+
+    for &(dims, offset) in cuboids {
+        add_cuboid(
+            &mut positions,
+            &mut uvs,
+            &mut normals,
+            &mut indices,
+            &mut index_offset,
+            dims,
+            offset,
+            v_top,
+            v_bottom,
+            num_tiles_x,
+        );
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// A cable running back to front with a band on the front half.
+/// Gate meshes face local +X, so the run is laid along X.
+pub fn create_diode_mesh(tile_row: usize) -> Mesh {
+    create_cuboid_mesh(
+        tile_row,
+        &[
+            ([1.0, 0.3, 0.3], [0.0, 0.0, 0.0]),    // cable run
+            ([0.25, 0.55, 0.55], [0.1, 0.0, 0.0]), // band marking the front
+        ],
+    )
+}
+
+/// Two cable runs through one cell: the Z run is straight, the X run bridges over it.
+pub fn create_crossing_mesh(tile_row: usize) -> Mesh {
+    create_cuboid_mesh(
+        tile_row,
+        &[
+            ([0.3, 0.3, 1.0], [0.0, 0.0, 0.0]),     // Z run
+            ([0.3, 0.3, 0.3], [0.35, 0.0, 0.0]),    // X run, +X stub
+            ([0.3, 0.3, 0.3], [-0.35, 0.0, 0.0]),   // X run, -X stub
+            ([0.8, 0.15, 0.3], [0.0, 0.225, 0.0]),  // X run, bridge
+        ],
+    )
+}
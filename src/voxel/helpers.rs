@@ -1,6 +1,7 @@
 use std::f32::consts::{FRAC_PI_2, PI};
 
 use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use bevy_rapier3d::prelude::Collider;
 
 use crate::prelude::*;
 
@@ -23,12 +24,35 @@ pub fn get_neighboring_coords(coord: IVec3) -> [IVec3; 6] {
 /// Computes the transform for a voxel based on its position and direction.
 pub fn compute_voxel_transform(voxel: &Voxel) -> Transform {
     let rotation_factor = get_voxel_rotation_factor(voxel);
-    let rotation_angle = rotation_factor * FRAC_PI_2 * voxel.direction as f32 + PI;
-    Transform {
+    let rotation_angle = rotation_factor * FRAC_PI_2 * direction_yaw(voxel.direction) as f32 + PI;
+    // Meshes face local +X, so tilting the front up or down is a turn about Z
+    let tilt_angle = rotation_factor * FRAC_PI_2 * direction_tilt(voxel.direction) as f32;
+    let mut transform = Transform {
         translation: voxel.position.as_vec3(),
-        rotation: Quat::from_rotation_y(rotation_angle),
+        rotation: Quat::from_rotation_y(rotation_angle) * Quat::from_rotation_z(tilt_angle),
         scale: Vec3::ONE,
+    };
+    // An open door collapses into a thin panel against one side of its cell.
+    if is_open_door(voxel) {
+        transform.scale.x = DOOR_OPEN_THICKNESS;
+        transform.translation += transform.rotation * Vec3::X * (1.0 - DOOR_OPEN_THICKNESS) * 0.5;
     }
+    transform
+}
+
+/// Returns true for a powered door, which is open and can be walked through.
+pub fn is_open_door(voxel: &Voxel) -> bool {
+    matches!(voxel.kind, VoxelType::Component(ComponentVariants::Door)) && voxel.state.any_set()
+}
+
+/// Returns true if the voxel should block the player (everything but open doors).
+pub fn voxel_is_solid(voxel: &Voxel) -> bool {
+    !is_open_door(voxel)
+}
+
+/// Collider shared by every solid voxel.
+pub fn voxel_collider() -> Collider {
+    Collider::cuboid(VOXEL_COLLIDER_SIZE, VOXEL_COLLIDER_SIZE, VOXEL_COLLIDER_SIZE)
 }
 
 
@@ -43,10 +67,99 @@ pub fn get_voxel_rotation_factor(voxel: &Voxel) -> f32 {
         VoxelType::Structural(_) => 0.0,
         VoxelType::Wire(_) => 0.0,
         VoxelType::BundledWire => 0.0,
+        VoxelType::WireCrossing => 0.0,
         _ => 1.0,
     }
 }
 
+/// `Voxel::direction` packs a yaw and a tilt: 1..=4 face horizontally (see `cardinalize`),
+/// 5..=8 face up and 9..=12 face down, keeping the yaw of `direction - 4` / `direction - 8`.
+pub fn direction_yaw(direction: u8) -> u8 {
+    direction.saturating_sub(1) % 4 + 1
+}
+
+/// +1 for a voxel facing up, -1 facing down, 0 facing horizontally.
+pub fn direction_tilt(direction: u8) -> i32 {
+    match direction.saturating_sub(1) / 4 {
+        1 => 1,
+        2 => -1,
+        _ => 0,
+    }
+}
+
+/// Direction after turning a placed voxel a quarter turn about Y, keeping its tilt.
+pub fn rotated_direction(direction: u8, reverse: bool) -> u8 {
+    let yaw = direction_yaw(direction);
+    let tilt_base = direction.saturating_sub(1) / 4 * 4;
+    let new_yaw = if reverse { (yaw + 2) % 4 + 1 } else { yaw % 4 + 1 };
+    tilt_base + new_yaw
+}
+
+/// Direction after reflecting a voxel in the plane through it with the given axis normal.
+/// Only the facing is reflected; ports beside the facing keep their handedness.
+pub fn mirrored_direction(direction: u8, normal: IVec3) -> u8 {
+    let yaw = direction_yaw(direction);
+    let tilt_base = direction.saturating_sub(1) / 4 * 4;
+    match (normal.x != 0, normal.y != 0) {
+        // +X (2) and -X (4) swap
+        (true, _) => tilt_base + if yaw.is_multiple_of(2) { 6 - yaw } else { yaw },
+        // up (5..=8) and down (9..=12) swap
+        (_, true) => match tilt_base {
+            4 => yaw + 8,
+            8 => yaw + 4,
+            _ => direction,
+        },
+        // +Z (1) and -Z (3) swap
+        _ => tilt_base + if yaw % 2 == 1 { 4 - yaw } else { yaw },
+    }
+}
+
+/// Turns an offset by `turns` quarter turns about Y, matching `rotated_direction`.
+pub fn rotated_offset(offset: IVec3, turns: u8) -> IVec3 {
+    (0..turns % 4).fold(offset, |v, _| IVec3::new(v.z, v.y, -v.x))
+}
+
+/// Insulated face mask (see `Voxel::insulated`) after moving every face offset
+/// through `transform`, e.g. the same turn or mirror applied to the position.
+pub fn transformed_insulation(insulated: u8, transform: impl Fn(IVec3) -> IVec3) -> u8 {
+    get_neighboring_coords(IVec3::ZERO)
+        .into_iter()
+        .enumerate()
+        .filter(|(face, _)| insulated & (1 << face) != 0)
+        .filter_map(|(_, offset)| face_index(transform(offset)))
+        .fold(0, |mask, face| mask | 1 << face)
+}
+
+/// Direction of a newly placed voxel. Horizontal from the camera yaw, or facing up / down
+/// following the camera pitch when `vertical` is requested.
+pub fn placement_direction(camera_dir: Vec3, vertical: bool) -> u8 {
+    let yaw = cardinalize(camera_dir);
+    match (vertical, camera_dir.y >= 0.0) {
+        (false, _)    => yaw,
+        (true, true)  => yaw + 4,
+        (true, false) => yaw + 8,
+    }
+}
+
+/// A newly placed voxel of `kind` with cleared state.
+/// Random blocks are seeded from the cell they are placed in.
+pub fn fresh_voxel(kind: VoxelType, position: IVec3, direction: u8) -> Voxel {
+    let kind = match kind {
+        VoxelType::Component(ComponentVariants::Random(_)) => {
+            VoxelType::Component(ComponentVariants::Random(position_seed(position)))
+        }
+        kind => kind,
+    };
+    Voxel {
+        kind,
+        position,
+        direction,
+        state: Bits16::all_zeros(),
+        insulated: 0,
+        extra_states: [Bits16::all_zeros(); MAX_OUTPUT_PORTS - 1],
+    }
+}
+
 /// Converts a 3D direction vector into one of four cardinal direction indices (1 through 4).
 /// Returns 1 by default if the horizontal component is negligible.
 pub fn cardinalize(dir: Vec3) -> u8 {
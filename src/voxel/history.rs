@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// A single reversible change to the voxel map.
+#[derive(Clone, Debug)]
+pub enum Edit {
+    Place(Voxel),
+    Remove(Voxel),
+    /// A quarter turn of the voxel at `position`, see `GameEvent::RotateBlock`.
+    Rotate { position: IVec3, reverse: bool },
+    /// Toggling the insulation of one face, see `GameEvent::ToggleInsulation`.
+    Insulate { position: IVec3, face: usize },
+    /// Labels of a voxel, recorded before it is removed so undoing the removal
+    /// brings them back. The removal itself drops them.
+    Unlabel(IVec3, Vec<VoxelLabel>),
+    Label(IVec3, Vec<VoxelLabel>),
+}
+
+impl Edit {
+    fn position(&self) -> IVec3 {
+        match self {
+            Edit::Place(voxel) | Edit::Remove(voxel) => voxel.position,
+            Edit::Rotate { position, .. } | Edit::Insulate { position, .. } => *position,
+            Edit::Unlabel(position, _) | Edit::Label(position, _) => *position,
+        }
+    }
+
+    fn inverse(self) -> Edit {
+        match self {
+            Edit::Place(voxel) => Edit::Remove(voxel),
+            Edit::Remove(voxel) => Edit::Place(voxel),
+            Edit::Rotate { position, reverse } => Edit::Rotate { position, reverse: !reverse },
+            // Toggling again undoes it
+            Edit::Insulate { .. } => self,
+            Edit::Unlabel(position, labels) => Edit::Label(position, labels),
+            Edit::Label(position, labels) => Edit::Unlabel(position, labels),
+        }
+    }
+
+    fn same_op(&self, other: &Edit) -> bool {
+        let same_kind = match (self, other) {
+            (Edit::Place(_), Edit::Place(_)) | (Edit::Remove(_), Edit::Remove(_)) => true,
+            (Edit::Rotate { reverse: a, .. }, Edit::Rotate { reverse: b, .. }) => a == b,
+            (Edit::Insulate { face: a, .. }, Edit::Insulate { face: b, .. }) => a == b,
+            _ => false,
+        };
+        same_kind && self.position() == other.position()
+    }
+}
+
+/// Undo and redo stacks of one world. Each step holds every edit that was made
+/// in one go, e.g. while a mouse button was held down.
+#[derive(Default)]
+pub struct WorldHistory {
+    undo: Vec<Vec<Edit>>,
+    redo: Vec<Vec<Edit>>,
+    group_open: bool,
+}
+
+impl WorldHistory {
+    fn record(&mut self, edits: Vec<Edit>, keep_open: bool) {
+        match self.undo.last_mut() {
+            Some(step) if self.group_open => step.extend(edits),
+            _ => self.undo.push(edits),
+        }
+        if self.undo.len() > EDIT_HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        self.group_open = keep_open;
+    }
+}
+
+/// Edit history of every world visited this session, keyed by world name.
+///
+/// Inserted once when the app is built rather than per world or per visit to
+/// the loading state, so leaving a world and loading it again keeps its undo steps.
+#[derive(Resource, Default)]
+pub struct EditHistory {
+    worlds: HashMap<String, WorldHistory>,
+    /// Edits sent by an undo / redo that must not be recorded again.
+    replaying: Vec<Edit>,
+}
+
+impl EditHistory {
+    fn world(&mut self, world_name: &str) -> &mut WorldHistory {
+        self.worlds.entry(world_name.to_string()).or_default()
+    }
+
+    fn take_replayed(&mut self, edit: &Edit) -> bool {
+        match self.replaying.iter().position(|replayed| replayed.same_op(edit)) {
+            Some(index) => {
+                self.replaying.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Records `PlaceBlock` / `RemoveBlock`, `RotateBlock` and `ToggleInsulation`
+/// events into the current world's history.
+///
+/// Runs after every `EditSenders` system and before `event_handler`, so it sees
+/// each edit in the frame it is sent and a removal can still read the voxel it
+/// is about to lose. Edits made while a mouse button stays held join the same step.
+pub fn record_edits(
+    mut game_events: EventReader<GameEvent>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    voxel_map: Res<VoxelMap>,
+    save_game: Res<SavedWorld>,
+    mut history: ResMut<EditHistory>,
+) {
+    let mut edits = Vec::new();
+    // Voxels as they will be once the earlier events of this frame are applied
+    let mut pending: HashMap<IVec3, Option<Voxel>> = HashMap::new();
+    for event in game_events.read() {
+        let position = match *event {
+            GameEvent::PlaceBlock { ref voxel, .. } => voxel.position,
+            GameEvent::RemoveBlock { position }
+            | GameEvent::RotateBlock { position, .. }
+            | GameEvent::ToggleInsulation { position, .. } => position,
+            _ => continue,
+        };
+        let current = pending
+            .get(&position)
+            .copied()
+            .unwrap_or_else(|| voxel_map.voxel_map.get(&position).copied());
+        let (edit, after) = match (event, current) {
+            (GameEvent::PlaceBlock { voxel, .. }, None) => (Edit::Place(*voxel), Some(*voxel)),
+            (GameEvent::RemoveBlock { .. }, Some(voxel)) => (Edit::Remove(voxel), None),
+            (&GameEvent::RotateBlock { reverse, .. }, Some(voxel)) => {
+                let direction = rotated_direction(voxel.direction, reverse);
+                (Edit::Rotate { position, reverse }, Some(Voxel { direction, ..voxel }))
+            }
+            (&GameEvent::ToggleInsulation { face, .. }, Some(voxel)) => {
+                let insulated = voxel.insulated ^ 1 << face;
+                (Edit::Insulate { position, face }, Some(Voxel { insulated, ..voxel }))
+            }
+            _ => continue,
+        };
+        pending.insert(position, after);
+        if history.take_replayed(&edit) {
+            continue;
+        }
+        if let Edit::Remove(_) = edit {
+            let labels: Vec<VoxelLabel> = save_game
+                .labels
+                .iter()
+                .filter(|label| label.position == position)
+                .cloned()
+                .collect();
+            if !labels.is_empty() {
+                edits.push(Edit::Unlabel(position, labels));
+            }
+        }
+        edits.push(edit);
+    }
+    // Replayed edits arrive in the frame they are sent; one that didn't apply
+    // must not swallow a later edit of the player
+    history.replaying.clear();
+
+    let holding = mouse.pressed(MouseButton::Left) || mouse.pressed(MouseButton::Right);
+    let world = history.world(&save_game.world_name);
+    if !edits.is_empty() {
+        world.record(edits, holding);
+    } else if !holding {
+        world.group_open = false;
+    }
+}
+
+/// Ctrl+Z undoes the last edit step, Ctrl+Y (or Ctrl+Shift+Z) redoes it.
+pub fn undo_redo_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_ui: Res<GameUI>,
+    voxel_map: Res<VoxelMap>,
+    save_game: Res<SavedWorld>,
+    mut history: ResMut<EditHistory>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    if *current_ui != GameUI::Default || !keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+    let shift = keyboard.pressed(KeyCode::ShiftLeft);
+    let undo = keyboard.just_pressed(KeyCode::KeyZ) && !shift;
+    let redo = keyboard.just_pressed(KeyCode::KeyY) || (keyboard.just_pressed(KeyCode::KeyZ) && shift);
+    if !undo && !redo {
+        return;
+    }
+
+    let world = history.world(&save_game.world_name);
+    world.group_open = false;
+    let step = if undo { world.undo.pop() } else { world.redo.pop() };
+    let Some(step) = step else {
+        return;
+    };
+
+    // Undo walks the step backwards applying the inverse of every edit
+    let edits: Vec<Edit> = if undo {
+        step.iter().rev().map(|edit| edit.clone().inverse()).collect()
+    } else {
+        step.clone()
+    };
+    if undo {
+        world.redo.push(step);
+    } else {
+        world.undo.push(step);
+    }
+
+    // The voxel map only changes once `event_handler` runs, so track the
+    // cells this step has already filled or emptied
+    let mut occupied: HashMap<IVec3, bool> = HashMap::new();
+    for edit in edits {
+        let position = edit.position();
+        let is_occupied = *occupied
+            .entry(position)
+            .or_insert_with(|| voxel_map.voxel_map.contains_key(&position));
+        match &edit {
+            Edit::Place(voxel) => {
+                // Something was built here since; leave it alone
+                if is_occupied {
+                    continue;
+                }
+                let Some(voxel_asset) = voxel_map.asset(voxel.kind).cloned() else {
+                    continue;
+                };
+                event_writer.send(GameEvent::PlaceBlock { voxel: *voxel, voxel_asset });
+            }
+            Edit::Remove(_) => {
+                if !is_occupied {
+                    continue;
+                }
+                event_writer.send(GameEvent::RemoveBlock { position });
+            }
+            Edit::Rotate { reverse, .. } => {
+                if !is_occupied {
+                    continue;
+                }
+                event_writer.send(GameEvent::RotateBlock { position, reverse: *reverse });
+            }
+            Edit::Insulate { face, .. } => {
+                if !is_occupied {
+                    continue;
+                }
+                event_writer.send(GameEvent::ToggleInsulation { position, face: *face });
+            }
+            // Follows the `Place` of its voxel, which was sent first
+            Edit::Label(_, labels) => {
+                if is_occupied {
+                    event_writer.send(GameEvent::AddLabels { labels: labels.clone() });
+                }
+                continue;
+            }
+            // The `Remove` that follows drops them
+            Edit::Unlabel(..) => continue,
+        }
+        occupied.insert(position, !matches!(edit, Edit::Remove(_)));
+        history.replaying.push(edit);
+        event_writer.send(GameEvent::UpdateMesh {
+            updates: get_neighboring_coords(position),
+        });
+    }
+}
@@ -1,4 +1,5 @@
 pub mod voxel;
 pub mod graphics;
 pub mod helpers;
-pub mod world; 
\ No newline at end of file
+pub mod world;
+pub mod history; 
\ No newline at end of file
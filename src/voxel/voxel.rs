@@ -33,20 +33,25 @@ fn spawn_voxel_entity(
         materials.add(cloned_material)
     };
 
-    commands
-        .spawn(VoxelBundle {
-            mesh: Mesh3d(asset.mesh_handle.clone()),
-            material: MeshMaterial3d(new_material_handle),
-            transform,
-            collider: Collider::cuboid(
-                VOXEL_COLLIDER_SIZE,
-                VOXEL_COLLIDER_SIZE,
-                VOXEL_COLLIDER_SIZE,
-            ),
-        })
-        .insert(GameEntity)
-        .insert(voxel)
-        .id()
+    let mut entity = commands.spawn(VoxelBundle {
+        mesh: Mesh3d(asset.mesh_handle.clone()),
+        material: MeshMaterial3d(new_material_handle),
+        transform,
+        collider: voxel_collider(),
+    });
+    entity.insert(GameEntity).insert(voxel);
+    if matches!(
+        voxel.kind,
+        VoxelType::Component(ComponentVariants::PressurePlate | ComponentVariants::ProximitySensor(_))
+    ) {
+        entity.insert(SensorVoxel);
+    }
+
+    // Open doors are loaded without a collider so the player can walk through.
+    if !voxel_is_solid(&voxel) {
+        entity.remove::<Collider>();
+    }
+    entity.id()
 }
 
 pub fn add_voxel(
@@ -62,16 +67,90 @@ pub fn add_voxel(
     let entity = spawn_voxel_entity(commands, voxel, &asset, materials);
     voxel_map.entity_map.insert(voxel.position, entity);
     voxel_map.voxel_map.insert(voxel.position, voxel);
+    voxel_map.generation += 1;
 }
 
 /// Removes the voxel entity at the given position.
 pub fn remove_voxel(commands: &mut Commands, voxel_map: &mut VoxelMap, position: IVec3) {
     if let Some(entity) = voxel_map.entity_map.remove(&position) {
         voxel_map.voxel_map.remove(&position);
+        voxel_map.generation += 1;
         commands.entity(entity).despawn();
     }
 }
 
+/// Moves the voxel at `from` into the empty cell `to`, keeping its entity, state
+/// and labels. The entity slides into place over `ACTUATOR_ANIMATION_TIME`.
+pub fn move_voxel(
+    commands: &mut Commands,
+    voxel_map: &mut VoxelMap,
+    labels: &mut [VoxelLabel],
+    from: IVec3,
+    to: IVec3,
+) {
+    if voxel_exists(voxel_map, to) {
+        return;
+    }
+    let (Some(entity), Some(mut voxel)) = (
+        voxel_map.entity_map.remove(&from),
+        voxel_map.voxel_map.remove(&from),
+    ) else {
+        return;
+    };
+
+    let start = compute_voxel_transform(&voxel);
+    voxel.position = to;
+    voxel_map.entity_map.insert(to, entity);
+    voxel_map.voxel_map.insert(to, voxel);
+    voxel_map.generation += 1;
+    for label in labels.iter_mut().filter(|label| label.position == from) {
+        label.position = to;
+    }
+
+    commands
+        .entity(entity)
+        .insert(voxel)
+        .insert(VoxelAnimation::new(start, compute_voxel_transform(&voxel)));
+}
+
+/// Interpolates a voxel entity between two transforms, used by pistons and doors.
+#[derive(Component)]
+pub struct VoxelAnimation {
+    pub start: Transform,
+    pub end: Transform,
+    pub timer: Timer,
+}
+
+impl VoxelAnimation {
+    pub fn new(start: Transform, end: Transform) -> Self {
+        Self {
+            start,
+            end,
+            timer: Timer::new(ACTUATOR_ANIMATION_TIME, TimerMode::Once),
+        }
+    }
+}
+
+/// Advances every running `VoxelAnimation` and removes it once it has finished.
+pub fn animate_voxels(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut VoxelAnimation)>,
+) {
+    for (entity, mut transform, mut animation) in query.iter_mut() {
+        animation.timer.tick(time.delta());
+        let t = animation.timer.fraction();
+
+        transform.translation = animation.start.translation.lerp(animation.end.translation, t);
+        transform.rotation = animation.start.rotation.slerp(animation.end.rotation, t);
+        transform.scale = animation.start.scale.lerp(animation.end.scale, t);
+
+        if animation.timer.finished() {
+            commands.entity(entity).remove::<VoxelAnimation>();
+        }
+    }
+}
+
 /// Creates a voxel material using the provided texture atlas.
 pub fn create_voxel_material(atlas_handle: Handle<Image>) -> StandardMaterial {
     StandardMaterial {
@@ -115,6 +194,7 @@ pub fn create_voxel_map(
         entity_map,
         voxel_map,
         asset_map: voxel_asset_map,
+        generation: 0,
     };
     
     voxel_map
@@ -143,19 +223,33 @@ pub fn count_neighbors(voxel: Voxel, voxel_map: &VoxelMap) -> [bool; 6] {
         // Skip empty space
         let Some(neigh_voxel) = voxel_map.voxel_map.get(pos) else { continue };
 
-        // ── 1. direct cable‑to‑cable decision ───────────────────────────────
+        // Insulated faces never connect
+        let dir = *pos - voxel.position;
+        if voxel.is_insulated(dir) {
+            continue;
+        }
+
+        // ── 1. a crossing only takes horizontal runs ────────────────────────
+        if matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire)
+        && matches!(neigh_voxel.kind, VoxelType::WireCrossing)
+        {
+            neighbors[i] = pos.y == voxel.position.y;
+            continue;
+        }
+
+        // ── 2. direct cable‑to‑cable decision ───────────────────────────────
         if matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire)
         && matches!(neigh_voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire)
         {
-            if cables_compatible(&voxel.kind, &neigh_voxel.kind) {
+            if cables_compatible(&voxel.kind, &neigh_voxel.kind) && !neigh_voxel.is_insulated(-dir) {
                 neighbors[i] = true;      // compatible channels or bundled
             }
             continue;                     // **never fall through** for cables
         }
 
-        // ── 2. Gate ↔ Cable / Gate ↔ Gate  (I/O aware) ─────────────────────
-        let (inputs, output) = voxel_directions(neigh_voxel);
-        if inputs.contains(&voxel.position) || output == voxel.position {
+        // ── 3. Gate ↔ Cable / Gate ↔ Gate  (I/O aware) ─────────────────────
+        let (inputs, outputs) = voxel_directions(neigh_voxel);
+        if inputs.contains(&voxel.position) || outputs.contains(&voxel.position) {
             neighbors[i] = true;
         }
     }
@@ -172,34 +266,41 @@ pub fn update_voxel_cable_mesh(
     meshes: &mut Assets<Mesh>,
     commands: &mut Commands,
 ) {
-    let connections = count_neighbors(*voxel, voxel_map);
-    let texture_row = voxel_map.asset_map[&voxel.kind].texture_row;
-    let new_mesh_handle = meshes.add(create_cable_mesh(texture_row, connections));
+    let new_mesh_handle = meshes.add(cable_mesh(voxel, voxel_map));
     commands.entity(entity).insert(Mesh3d(new_mesh_handle));
 }
 
+/// Builds the mesh of a voxel that `uses_cable_mesh`.
+/// Diodes and crossings have a fixed shape, plain cables follow their neighbors.
+pub fn cable_mesh(voxel: &Voxel, voxel_map: &VoxelMap) -> Mesh {
+    let texture_row = voxel_map
+        .asset(voxel.kind)
+        .map(|asset| asset.texture_row)
+        .unwrap_or_default();
+
+    match voxel.kind {
+        VoxelType::Diode => create_diode_mesh(texture_row),
+        VoxelType::WireCrossing => create_crossing_mesh(texture_row),
+        _ => create_cable_mesh(texture_row, count_neighbors(*voxel, voxel_map)),
+    }
+}
+
 /// Updates meshes, especially cables which need to change mesh to connect to those around them.
+///
+/// Works from the voxel map alone, so voxels spawned earlier in the same frame
+/// (whose commands have not been applied yet) are refreshed as well.
 pub fn update_meshes(
     voxel_positions: [IVec3; 6],
     voxel_map: &VoxelMap,
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
-    query: &mut Query<(Entity, &Voxel)>,
 ) {
     for pos in &voxel_positions {
-        if let Some(entity) = voxel_map.entity_map.get(pos) {
-            if let Ok((entity, voxel)) = query.get_mut(*entity) {
-                // Use the helper function for cable voxel checks.
-                match voxel.kind {
-                    VoxelType::BundledWire => {
-                        update_voxel_cable_mesh(entity, voxel, voxel_map, meshes, commands);
-                    }
-                    VoxelType::Wire(_) => {
-                        update_voxel_cable_mesh(entity, voxel, voxel_map, meshes, commands);
-                    }
-                    _ => {}
-                }
-            }
+        let (Some(&entity), Some(voxel)) = (voxel_map.entity_map.get(pos), voxel_map.voxel_map.get(pos)) else {
+            continue;
+        };
+        if matches!(voxel.kind, VoxelType::Wire(_) | VoxelType::BundledWire) {
+            update_voxel_cable_mesh(entity, voxel, voxel_map, meshes, commands);
         }
     }
-}
\ No newline at end of file
+}
@@ -10,6 +10,21 @@ pub fn setup_world(
     mut materials: ResMut<Assets<StandardMaterial>>,
     image_handles: Res<GameTextures>,
 ) {
+    // Fresh simulation bookkeeping for the world being entered
+    commands.insert_resource(BlockUpdates::default());
+    commands.insert_resource(GateMemory::default());
+    commands.insert_resource(ChipInstances::default());
+    commands.insert_resource(Selection::default());
+    commands.insert_resource(ActiveTool::default());
+    commands.insert_resource(ArraySettings::default());
+    commands.insert_resource(Symmetry::default());
+    commands.insert_resource(LineDrag::default());
+    commands.insert_resource(Router::default());
+    commands.insert_resource(ChipDraft::default());
+    commands.insert_resource(LabelTarget::default());
+    commands.insert_resource(NetHighlight::default());
+    commands.insert_resource(ToolMessage::default());
+
     // Spawn a directional light (Sun)
     commands
         .spawn((